@@ -0,0 +1,114 @@
+use crate::config::data_dir;
+use crate::translate::TranslateResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    result: TranslateResult,
+    /// Unix seconds when the result was fetched, used for the TTL
+    created: u64,
+    /// Unix seconds of the last hit, used for LRU eviction
+    last_used: u64,
+}
+
+/// On-disk LRU cache of successful translations, persisted as `cache.json`
+#[derive(Debug, Default)]
+pub struct ResultCache {
+    entries: HashMap<String, CacheEntry>,
+    dirty: bool,
+}
+
+fn cache_path() -> PathBuf {
+    data_dir().join("cache.json")
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Cache key: service + language pair + text with whitespace collapsed
+pub fn cache_key(service: &str, source: &str, target: &str, text: &str) -> String {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    format!("{}|{}|{}|{}", service, source, target, normalized)
+}
+
+impl ResultCache {
+    pub fn load() -> Self {
+        let data = fs::read_to_string(cache_path()).unwrap_or_default();
+        Self {
+            entries: serde_json::from_str(&data).unwrap_or_default(),
+            dirty: false,
+        }
+    }
+
+    /// Write to disk if anything changed since the last save
+    pub fn save(&mut self) {
+        if !self.dirty {
+            return;
+        }
+        if let Ok(data) = serde_json::to_string(&self.entries) {
+            fs::write(cache_path(), data).ok();
+        }
+        self.dirty = false;
+    }
+
+    pub fn get(&mut self, key: &str, ttl_secs: u64) -> Option<TranslateResult> {
+        let now = now_secs();
+        let expired = match self.entries.get_mut(key) {
+            Some(entry) if now.saturating_sub(entry.created) <= ttl_secs => {
+                entry.last_used = now;
+                self.dirty = true;
+                return Some(entry.result.clone());
+            }
+            Some(_) => true,
+            None => false,
+        };
+        if expired {
+            self.entries.remove(key);
+            self.dirty = true;
+        }
+        None
+    }
+
+    /// Store a successful result, evicting least recently used entries past `max_entries`
+    pub fn insert(&mut self, key: String, result: TranslateResult, max_entries: usize) {
+        if result.error.is_some() || max_entries == 0 {
+            return;
+        }
+        let now = now_secs();
+        self.entries.insert(
+            key,
+            CacheEntry {
+                result,
+                created: now,
+                last_used: now,
+            },
+        );
+        if self.entries.len() > max_entries {
+            let mut by_age: Vec<(u64, String)> = self
+                .entries
+                .iter()
+                .map(|(k, e)| (e.last_used, k.clone()))
+                .collect();
+            by_age.sort();
+            let excess = self.entries.len() - max_entries;
+            for (_, k) in by_age.into_iter().take(excess) {
+                self.entries.remove(&k);
+            }
+        }
+        self.dirty = true;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.dirty = true;
+        self.save();
+    }
+}
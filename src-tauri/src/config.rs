@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -9,6 +10,14 @@ pub struct AppConfig {
     pub general: GeneralConfig,
     #[serde(default)]
     pub select_translate: SelectTranslateConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub dictionary: DictionaryConfig,
+    #[serde(default)]
+    pub prompts: PromptConfig,
+    #[serde(default)]
+    pub usage: UsageConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,19 +33,168 @@ pub struct HotkeyConfig {
     pub screenshot_translate: HotkeyEntry,
 }
 
+/// Per-service settings. Missing entries fall back to their defaults so a
+/// config written before a provider existed still loads.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ServicesConfig {
-    pub google: ServiceEntry,
-    pub deepl: ServiceEntry,
-    pub bing: ServiceEntry,
+    pub google: GoogleServiceEntry,
+    pub deepl: DeepLServiceEntry,
+    pub bing: BingServiceEntry,
     pub baidu: BaiduServiceEntry,
-    pub openai: OpenAIServiceEntry,
+    pub libre: LibreServiceEntry,
+    pub youdao: YoudaoServiceEntry,
+    pub tencent: TencentServiceEntry,
+    pub volcano: VolcanoServiceEntry,
+    /// `api_key` holds the Caiyun token
+    pub caiyun: ServiceEntry,
+    pub anthropic: LlmServiceEntry,
+    pub gemini: LlmServiceEntry,
+    /// Named OpenAI-compatible endpoints; each enabled one gets its own result card
+    pub llm_profiles: Vec<LlmProfile>,
+    /// Single-endpoint setting from older configs, moved into `llm_profiles` on load
+    #[serde(skip_serializing)]
+    pub openai: Option<OpenAIServiceEntry>,
+    /// Request policy per service id; services not listed use `RequestPolicy::default()`
+    pub policies: HashMap<String, RequestPolicy>,
+}
+
+impl ServicesConfig {
+    /// The instance's own policy, else its provider's, else the default
+    pub fn policy(&self, key: &str, id: &str) -> RequestPolicy {
+        self.policies
+            .get(key)
+            .or_else(|| self.policies.get(id))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Switch the service with this instance key on or off. Returns whether
+    /// anything changed; unknown keys change nothing.
+    pub fn set_enabled(&mut self, key: &str, enabled: bool) -> bool {
+        let flag = match key {
+            "google" => &mut self.google.enabled,
+            "deepl" => &mut self.deepl.enabled,
+            "bing" => &mut self.bing.enabled,
+            "baidu" => &mut self.baidu.enabled,
+            "libre" => &mut self.libre.enabled,
+            "youdao" => &mut self.youdao.enabled,
+            "tencent" => &mut self.tencent.enabled,
+            "volcano" => &mut self.volcano.enabled,
+            "caiyun" => &mut self.caiyun.enabled,
+            "anthropic" => &mut self.anthropic.enabled,
+            "gemini" => &mut self.gemini.enabled,
+            _ => {
+                let profile = key
+                    .strip_prefix("openai:")
+                    .and_then(|id| self.llm_profiles.iter_mut().find(|p| p.id == id));
+                match profile {
+                    Some(profile) => &mut profile.enabled,
+                    None => return false,
+                }
+            }
+        };
+        let changed = *flag != enabled;
+        *flag = enabled;
+        changed
+    }
+
+    /// Turn a legacy `openai` entry into the profile of the same id
+    fn migrate_openai(&mut self) {
+        let Some(legacy) = self.openai.take() else {
+            return;
+        };
+        self.llm_profiles.retain(|p| p.id != "openai");
+        self.llm_profiles.insert(
+            0,
+            LlmProfile {
+                enabled: legacy.enabled,
+                api_key: legacy.api_key,
+                api_url: legacy.api_url,
+                model: legacy.model,
+                ..LlmProfile::default()
+            },
+        );
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceEntry {
     pub enabled: bool,
     pub api_key: String,
+    /// Proxy or mirror to use instead of the provider's endpoint; empty for the official one
+    #[serde(default)]
+    pub base_url: String,
+}
+
+/// Without an API key the free web endpoint is used; with one, the official
+/// Cloud Translation API (v3 when `project_id` is set, otherwise v2)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoogleServiceEntry {
+    pub enabled: bool,
+    /// API key, or an OAuth access token for v3 projects that don't accept keys
+    pub api_key: String,
+    #[serde(default)]
+    pub project_id: String,
+    /// v3 location; glossaries need a regional one such as "us-central1"
+    #[serde(default = "default_google_location")]
+    pub location: String,
+    /// v3 glossary id or full resource name, used when the source language is known
+    #[serde(default)]
+    pub glossary_id: String,
+    /// Replaces the web endpoint, or the Cloud Translation one once a key is set
+    #[serde(default)]
+    pub base_url: String,
+}
+
+fn default_google_location() -> String {
+    "global".to_string()
+}
+
+/// Without an API key the keyless token flow of the Edge browser is used
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BingServiceEntry {
+    pub enabled: bool,
+    /// Azure Translator resource key
+    pub api_key: String,
+    /// Azure region of the resource (e.g. "eastasia"); empty for global resources
+    #[serde(default)]
+    pub region: String,
+    /// Replaces the Translator endpoint; without a key the Edge token is then
+    /// fetched from `{base_url}/translate/auth` as well
+    #[serde(default)]
+    pub base_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeepLServiceEntry {
+    pub enabled: bool,
+    pub api_key: String,
+    /// "default", "more", "less", "prefer_more" or "prefer_less"; the
+    /// `prefer_` forms fall back quietly for targets without formality
+    #[serde(default = "default_formality")]
+    pub formality: String,
+    /// Target used for English: "EN-US" or "EN-GB"
+    #[serde(default = "default_english_variant")]
+    pub english_variant: String,
+    /// Target used for Portuguese: "PT-BR" or "PT-PT"
+    #[serde(default = "default_portuguese_variant")]
+    pub portuguese_variant: String,
+    /// Replaces api.deepl.com / api-free.deepl.com
+    #[serde(default)]
+    pub base_url: String,
+}
+
+fn default_formality() -> String {
+    "default".to_string()
+}
+
+fn default_english_variant() -> String {
+    "EN-US".to_string()
+}
+
+fn default_portuguese_variant() -> String {
+    "PT-BR".to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +202,41 @@ pub struct BaiduServiceEntry {
     pub enabled: bool,
     pub app_id: String,
     pub secret_key: String,
+    /// Replaces https://fanyi-api.baidu.com
+    #[serde(default)]
+    pub base_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YoudaoServiceEntry {
+    pub enabled: bool,
+    pub app_key: String,
+    pub app_secret: String,
+    /// Replaces https://openapi.youdao.com
+    #[serde(default)]
+    pub base_url: String,
+}
+
+/// Tencent Cloud API key pair, plus the region requests are sent to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TencentServiceEntry {
+    pub enabled: bool,
+    pub secret_id: String,
+    pub secret_key: String,
+    pub region: String,
+    /// Requests go here; they are still signed for tmt.tencentcloudapi.com
+    #[serde(default)]
+    pub base_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VolcanoServiceEntry {
+    pub enabled: bool,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Requests go here; they are still signed for translate.volcengineapi.com
+    #[serde(default)]
+    pub base_url: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +247,137 @@ pub struct OpenAIServiceEntry {
     pub model: String,
 }
 
+/// A LibreTranslate server, or any engine with the same `/translate` and
+/// `/languages` API
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LibreServiceEntry {
+    pub enabled: bool,
+    pub base_url: String,
+    /// Only needed by servers started with API keys required
+    pub api_key: String,
+}
+
+/// A hosted LLM with its own API (Anthropic, Gemini). `api_url` can point at
+/// a proxy or a local mock server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmServiceEntry {
+    pub enabled: bool,
+    pub api_key: String,
+    pub api_url: String,
+    pub model: String,
+    #[serde(default = "default_temperature")]
+    pub temperature: f32,
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: u32,
+}
+
+fn default_temperature() -> f32 {
+    0.3
+}
+
+fn default_max_tokens() -> u32 {
+    4096
+}
+
+/// One OpenAI-compatible chat completions endpoint (OpenAI, Ollama, vLLM...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LlmProfile {
+    /// Unique among profiles; keys the profile's rate limiter
+    pub id: String,
+    /// Shown on the result card
+    pub name: String,
+    pub enabled: bool,
+    pub api_url: String,
+    /// Sent as a bearer token when set; local servers usually need none
+    pub api_key: String,
+    pub model: String,
+    pub temperature: f32,
+    pub max_tokens: u32,
+    /// Translate-mode system prompt for this profile only; empty uses the shared template
+    pub system_prompt: String,
+}
+
+impl Default for LlmProfile {
+    fn default() -> Self {
+        Self {
+            id: "openai".to_string(),
+            name: "OpenAI".to_string(),
+            enabled: false,
+            api_url: "https://api.openai.com/v1/chat/completions".to_string(),
+            api_key: String::new(),
+            model: "gpt-4o-mini".to_string(),
+            temperature: default_temperature(),
+            max_tokens: default_max_tokens(),
+            system_prompt: String::new(),
+        }
+    }
+}
+
+/// System and user message templates for one prompt mode. Both take the
+/// `{source}`, `{target}`, `{text}`, `{glossary}` and `{context}` variables.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PromptTemplate {
+    pub system: String,
+    pub user: String,
+}
+
+/// User overrides of the built-in prompt templates, keyed by mode id.
+/// Blank or missing fields fall back to the built-in template.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PromptConfig {
+    pub templates: HashMap<String, PromptTemplate>,
+}
+
+/// Monthly character budgets per service and what happens as one runs out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UsageConfig {
+    /// Warn once a service has used this percentage of its limit
+    pub warn_percent: u8,
+    /// Turn a service off when it reaches its limit
+    pub auto_disable: bool,
+    /// Characters per month by service key; DeepL falls back to the limit its API reports
+    pub limits: HashMap<String, u64>,
+}
+
+impl Default for UsageConfig {
+    fn default() -> Self {
+        Self {
+            warn_percent: 80,
+            auto_disable: false,
+            limits: HashMap::new(),
+        }
+    }
+}
+
+/// Timeout, retry and rate limit applied to every call to one service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RequestPolicy {
+    /// Per attempt, 0 disables the timeout
+    pub timeout_secs: u64,
+    /// Extra attempts after a transient failure (network, timeout, 5xx, 429)
+    pub max_retries: u32,
+    /// Sustained requests per second, 0 disables the limiter
+    pub rate_per_sec: f64,
+    /// Requests allowed back to back before the rate applies
+    pub burst: u32,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 15,
+            max_retries: 2,
+            rate_per_sec: 5.0,
+            burst: 5,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GeneralConfig {
     pub theme: String,
@@ -61,6 +385,54 @@ pub struct GeneralConfig {
     pub source_lang: String,
     pub target_lang: String,
     pub always_on_top: bool,
+    /// Max translation history entries kept on disk, 0 disables history
+    #[serde(default = "default_history_limit")]
+    pub history_limit: usize,
+    /// "auto" target for text in any other language when no rule matches;
+    /// empty in configs saved before it existed, see `migrate_first_lang`
+    #[serde(default)]
+    pub first_lang: String,
+    /// "auto" target for text already in `first_lang`
+    #[serde(default = "default_second_lang")]
+    pub second_lang: String,
+    /// Checked in order before the first/second language fallback
+    #[serde(default)]
+    pub target_rules: Vec<TargetRule>,
+}
+
+impl GeneralConfig {
+    /// Seed `first_lang` from the old fixed `target_lang`, so "auto" keeps
+    /// translating into the language the user had picked
+    fn migrate_first_lang(&mut self) {
+        if !self.first_lang.is_empty() {
+            return;
+        }
+        self.first_lang = if self.target_lang.is_empty() || self.target_lang == "auto" {
+            default_first_lang()
+        } else {
+            self.target_lang.clone()
+        };
+    }
+}
+
+fn default_history_limit() -> usize {
+    500
+}
+
+fn default_first_lang() -> String {
+    "zh-CN".to_string()
+}
+
+fn default_second_lang() -> String {
+    "en".to_string()
+}
+
+/// Sends text detected as `source` to `target` when the target is "auto".
+/// `source` is a language code, a base code such as "zh", or "*" for any.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TargetRule {
+    pub source: String,
+    pub target: String,
 }
 
 /// Select-to-translate configuration
@@ -82,6 +454,136 @@ impl Default for SelectTranslateConfig {
     }
 }
 
+/// Local result cache, consulted before calling any service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    pub ttl_hours: u64,
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_hours: 24 * 7,
+            max_entries: 2000,
+        }
+    }
+}
+
+/// Offline dictionaries used for single-word lookups.
+/// Each path is a StarDict `.ifo`, a dictd `.index`, or a directory of them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DictionaryConfig {
+    pub enabled: bool,
+    pub paths: Vec<String>,
+}
+
+impl Default for DictionaryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            paths: Vec::new(),
+        }
+    }
+}
+
+impl Default for ServicesConfig {
+    fn default() -> Self {
+        Self {
+            google: GoogleServiceEntry {
+                enabled: true,
+                api_key: String::new(),
+                project_id: String::new(),
+                location: default_google_location(),
+                glossary_id: String::new(),
+                base_url: String::new(),
+            },
+            deepl: DeepLServiceEntry {
+                enabled: false,
+                api_key: String::new(),
+                formality: default_formality(),
+                english_variant: default_english_variant(),
+                portuguese_variant: default_portuguese_variant(),
+                base_url: String::new(),
+            },
+            bing: BingServiceEntry {
+                enabled: true,
+                api_key: String::new(),
+                region: String::new(),
+                base_url: String::new(),
+            },
+            baidu: BaiduServiceEntry {
+                enabled: false,
+                app_id: String::new(),
+                secret_key: String::new(),
+                base_url: String::new(),
+            },
+            libre: LibreServiceEntry {
+                enabled: false,
+                base_url: "http://localhost:5000".to_string(),
+                api_key: String::new(),
+            },
+            youdao: YoudaoServiceEntry {
+                enabled: false,
+                app_key: String::new(),
+                app_secret: String::new(),
+                base_url: String::new(),
+            },
+            tencent: TencentServiceEntry {
+                enabled: false,
+                secret_id: String::new(),
+                secret_key: String::new(),
+                region: "ap-guangzhou".to_string(),
+                base_url: String::new(),
+            },
+            volcano: VolcanoServiceEntry {
+                enabled: false,
+                access_key_id: String::new(),
+                secret_access_key: String::new(),
+                base_url: String::new(),
+            },
+            caiyun: ServiceEntry {
+                enabled: false,
+                api_key: String::new(),
+                base_url: String::new(),
+            },
+            anthropic: LlmServiceEntry {
+                enabled: false,
+                api_key: String::new(),
+                api_url: "https://api.anthropic.com/v1/messages".to_string(),
+                model: "claude-3-5-haiku-latest".to_string(),
+                temperature: default_temperature(),
+                max_tokens: default_max_tokens(),
+            },
+            gemini: LlmServiceEntry {
+                enabled: false,
+                api_key: String::new(),
+                api_url: "https://generativelanguage.googleapis.com/v1beta".to_string(),
+                model: "gemini-2.0-flash".to_string(),
+                temperature: default_temperature(),
+                max_tokens: default_max_tokens(),
+            },
+            llm_profiles: vec![LlmProfile::default()],
+            openai: None,
+            // LLMs stream long answers slowly
+            policies: ["openai", "anthropic", "gemini"]
+                .into_iter()
+                .map(|id| {
+                    let policy = RequestPolicy {
+                        timeout_secs: 120,
+                        max_retries: 1,
+                        rate_per_sec: 1.0,
+                        burst: 3,
+                    };
+                    (id.to_string(), policy)
+                })
+                .collect(),
+        }
+    }
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
@@ -99,56 +601,48 @@ impl Default for AppConfig {
                     shortcut: "Alt+S".to_string(),
                 },
             },
-            services: ServicesConfig {
-                google: ServiceEntry {
-                    enabled: true,
-                    api_key: String::new(),
-                },
-                deepl: ServiceEntry {
-                    enabled: false,
-                    api_key: String::new(),
-                },
-                bing: ServiceEntry {
-                    enabled: true,
-                    api_key: String::new(),
-                },
-                baidu: BaiduServiceEntry {
-                    enabled: false,
-                    app_id: String::new(),
-                    secret_key: String::new(),
-                },
-                openai: OpenAIServiceEntry {
-                    enabled: false,
-                    api_key: String::new(),
-                    api_url: "https://api.openai.com/v1/chat/completions".to_string(),
-                    model: "gpt-4o-mini".to_string(),
-                },
-            },
+            services: ServicesConfig::default(),
             general: GeneralConfig {
                 theme: "auto".to_string(),
                 auto_start: false,
                 source_lang: "auto".to_string(),
                 target_lang: "zh-CN".to_string(),
                 always_on_top: false,
+                history_limit: default_history_limit(),
+                first_lang: default_first_lang(),
+                second_lang: default_second_lang(),
+                target_rules: Vec::new(),
             },
             select_translate: SelectTranslateConfig::default(),
+            cache: CacheConfig::default(),
+            dictionary: DictionaryConfig::default(),
+            prompts: PromptConfig::default(),
+            usage: UsageConfig::default(),
         }
     }
 }
 
-fn config_path() -> PathBuf {
+/// `%APPDATA%/EasyDictWin`, also home to the other persisted stores
+pub fn data_dir() -> PathBuf {
     let dir = dirs::config_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join("EasyDictWin");
     fs::create_dir_all(&dir).ok();
-    dir.join("config.json")
+    dir
+}
+
+fn config_path() -> PathBuf {
+    data_dir().join("config.json")
 }
 
 pub fn load_config() -> AppConfig {
     let path = config_path();
     if path.exists() {
         let data = fs::read_to_string(&path).unwrap_or_default();
-        serde_json::from_str(&data).unwrap_or_default()
+        let mut config: AppConfig = serde_json::from_str(&data).unwrap_or_default();
+        config.services.migrate_openai();
+        config.general.migrate_first_lang();
+        config
     } else {
         let config = AppConfig::default();
         save_config(&config);
@@ -162,3 +656,34 @@ pub fn save_config(config: &AppConfig) {
         fs::write(&path, data).ok();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn general(json: serde_json::Value) -> GeneralConfig {
+        let mut general: GeneralConfig = serde_json::from_value(json).unwrap();
+        general.migrate_first_lang();
+        general
+    }
+
+    #[test]
+    fn first_lang_is_seeded_from_target_lang() {
+        let old = serde_json::json!({
+            "theme": "auto",
+            "auto_start": false,
+            "source_lang": "auto",
+            "target_lang": "ja",
+            "always_on_top": false,
+        });
+        assert_eq!(general(old.clone()).first_lang, "ja");
+
+        let mut auto = old.clone();
+        auto["target_lang"] = "auto".into();
+        assert_eq!(general(auto).first_lang, "zh-CN");
+
+        let mut saved = old;
+        saved["first_lang"] = "de".into();
+        assert_eq!(general(saved).first_lang, "de");
+    }
+}
@@ -0,0 +1,683 @@
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Structured dictionary entry shown above the machine translations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DictionaryEntry {
+    pub word: String,
+    /// Name of the dictionary (or service) the entry came from
+    pub source: String,
+    pub phonetics: Vec<String>,
+    pub meanings: Vec<Meaning>,
+    pub examples: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Meaning {
+    /// e.g. "n.", "vt.", "adj."; empty when the dictionary doesn't say
+    pub part_of_speech: String,
+    pub definitions: Vec<String>,
+}
+
+impl DictionaryEntry {
+    /// Plain-text rendering, one line per part of speech
+    pub fn summary(&self) -> String {
+        self.meanings
+            .iter()
+            .map(|m| {
+                let defs = m.definitions.join("; ");
+                if m.part_of_speech.is_empty() {
+                    defs
+                } else {
+                    format!("{} {}", m.part_of_speech, defs)
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Whether the input should get a dictionary lookup: one token of letters
+pub fn looks_like_word(text: &str) -> bool {
+    let text = text.trim();
+    !text.is_empty()
+        && text.chars().count() <= 32
+        && text
+            .chars()
+            .all(|c| c.is_alphabetic() || c == '-' || c == '\'')
+}
+
+// ==================== Loaded Dictionaries ====================
+
+enum Format {
+    /// StarDict; `sametypesequence` from the .ifo, empty if entries carry type bytes
+    StarDict { sametypesequence: String },
+    /// dictd (.index + .dict), plain text entries
+    Dictd,
+}
+
+enum DictData {
+    /// Uncompressed .dict, read on demand
+    File(PathBuf),
+    /// .dict.dz decompressed at load time
+    Memory(Vec<u8>),
+}
+
+impl DictData {
+    fn open(dict_path: &Path) -> Result<Self, String> {
+        if dict_path.extension().is_some_and(|e| e == "dz") {
+            let file = File::open(dict_path).map_err(|e| format!("Open error: {}", e))?;
+            let mut buf = Vec::new();
+            GzDecoder::new(file)
+                .read_to_end(&mut buf)
+                .map_err(|e| format!("Decompress error: {}", e))?;
+            Ok(DictData::Memory(buf))
+        } else {
+            Ok(DictData::File(dict_path.to_path_buf()))
+        }
+    }
+
+    /// `size` bytes at `offset`; `None` when that reaches past the data, as a
+    /// corrupt index may claim
+    fn read(&self, offset: u64, size: u32) -> Option<Vec<u8>> {
+        let end = offset.checked_add(size as u64)?;
+        match self {
+            DictData::Memory(buf) => {
+                let range = usize::try_from(offset).ok()?..usize::try_from(end).ok()?;
+                buf.get(range).map(|s| s.to_vec())
+            }
+            DictData::File(path) => {
+                let mut file = File::open(path).ok()?;
+                if end > file.metadata().ok()?.len() {
+                    return None;
+                }
+                file.seek(SeekFrom::Start(offset)).ok()?;
+                let mut out = vec![0u8; size as usize];
+                file.read_exact(&mut out).ok()?;
+                Some(out)
+            }
+        }
+    }
+}
+
+struct Dictionary {
+    name: String,
+    format: Format,
+    /// Lowercased headword -> (offset, size) in the data file
+    index: HashMap<String, Vec<(u64, u32)>>,
+    data: DictData,
+}
+
+/// All dictionaries listed in `DictionaryConfig::paths`, searched in order
+#[derive(Default)]
+pub struct Dictionaries {
+    dicts: Vec<Dictionary>,
+}
+
+impl Dictionaries {
+    /// Load every dictionary found at `paths`. Each path may be a StarDict `.ifo`,
+    /// a dictd `.index`, or a directory containing either; broken ones are skipped.
+    pub fn load(paths: &[String]) -> Self {
+        let mut dicts = Vec::new();
+        for path in paths {
+            for file in dictionary_files(Path::new(path)) {
+                match load_dictionary(&file) {
+                    Ok(dict) => {
+                        eprintln!("[Dict] Loaded {} ({} words)", dict.name, dict.index.len());
+                        dicts.push(dict);
+                    }
+                    Err(e) => eprintln!("[Dict] Failed to load {}: {}", file.display(), e),
+                }
+            }
+        }
+        Self { dicts }
+    }
+
+    /// First match across the loaded dictionaries, case-insensitive
+    pub fn lookup(&self, word: &str) -> Option<DictionaryEntry> {
+        let key = word.trim().to_lowercase();
+        self.dicts.iter().find_map(|dict| {
+            let locations = dict.index.get(&key)?;
+            let mut entry = DictionaryEntry {
+                word: word.trim().to_string(),
+                source: dict.name.clone(),
+                ..Default::default()
+            };
+            for &(offset, size) in locations {
+                let raw = dict.data.read(offset, size)?;
+                match &dict.format {
+                    Format::StarDict { sametypesequence } => {
+                        for (kind, text) in stardict_fields(&raw, sametypesequence) {
+                            match kind {
+                                't' => entry.phonetics.push(text),
+                                'g' | 'h' | 'x' | 'k' => parse_text(&strip_markup(&text), &mut entry),
+                                _ => parse_text(&text, &mut entry),
+                            }
+                        }
+                    }
+                    Format::Dictd => {
+                        let text = String::from_utf8_lossy(&raw);
+                        // dictd entries usually repeat the headword on the first line
+                        let body = match text.split_once('\n') {
+                            Some((first, rest)) if first.trim().eq_ignore_ascii_case(&key) => rest,
+                            _ => &text,
+                        };
+                        parse_text(body, &mut entry);
+                    }
+                }
+            }
+            (!entry.meanings.is_empty() || !entry.phonetics.is_empty()).then_some(entry)
+        })
+    }
+}
+
+/// Expand a configured path into the index files it refers to
+fn dictionary_files(path: &Path) -> Vec<PathBuf> {
+    let is_index = |p: &Path| p.extension().is_some_and(|e| e == "ifo" || e == "index");
+    if path.is_dir() {
+        let mut files: Vec<PathBuf> = fs::read_dir(path)
+            .map(|rd| rd.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+            .unwrap_or_default();
+        files.retain(|p| is_index(p));
+        files.sort();
+        files
+    } else if is_index(path) {
+        vec![path.to_path_buf()]
+    } else {
+        Vec::new()
+    }
+}
+
+/// `foo.ifo` -> `foo.idx` etc., preferring the uncompressed variant
+fn sibling(path: &Path, exts: &[&str]) -> Option<PathBuf> {
+    exts.iter()
+        .map(|ext| path.with_extension(ext))
+        .find(|p| p.exists())
+}
+
+fn load_dictionary(path: &Path) -> Result<Dictionary, String> {
+    if path.extension().is_some_and(|e| e == "ifo") {
+        load_stardict(path)
+    } else {
+        load_dictd(path)
+    }
+}
+
+// ==================== StarDict ====================
+
+fn load_stardict(ifo_path: &Path) -> Result<Dictionary, String> {
+    let ifo = fs::read_to_string(ifo_path).map_err(|e| format!("Read error: {}", e))?;
+    if !ifo.starts_with("StarDict's dict ifo file") {
+        return Err("Not a StarDict .ifo file".to_string());
+    }
+    let info: HashMap<&str, &str> = ifo
+        .lines()
+        .filter_map(|l| l.split_once('='))
+        .map(|(k, v)| (k.trim(), v.trim()))
+        .collect();
+
+    let name = info
+        .get("bookname")
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| ifo_path.file_stem().unwrap_or_default().to_string_lossy().into_owned());
+    let offset_bits = info.get("idxoffsetbits").copied().unwrap_or("32");
+    let sametypesequence = info.get("sametypesequence").copied().unwrap_or("").to_string();
+
+    let idx_path = sibling(ifo_path, &["idx", "idx.gz"]).ok_or("Missing .idx file")?;
+    let dict_path = sibling(ifo_path, &["dict", "dict.dz"]).ok_or("Missing .dict file")?;
+
+    let mut idx = Vec::new();
+    let mut file = File::open(&idx_path).map_err(|e| format!("Open error: {}", e))?;
+    if idx_path.extension().is_some_and(|e| e == "gz") {
+        GzDecoder::new(file).read_to_end(&mut idx)
+    } else {
+        file.read_to_end(&mut idx)
+    }
+    .map_err(|e| format!("Read error: {}", e))?;
+
+    let offset_len = if offset_bits == "64" { 8 } else { 4 };
+    let mut index: HashMap<String, Vec<(u64, u32)>> = HashMap::new();
+    let mut pos = 0;
+    while pos < idx.len() {
+        let end = idx[pos..]
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or("Truncated .idx file")?;
+        let word = String::from_utf8_lossy(&idx[pos..pos + end]).to_lowercase();
+        pos += end + 1;
+        if pos + offset_len + 4 > idx.len() {
+            return Err("Truncated .idx file".to_string());
+        }
+        let offset = idx[pos..pos + offset_len]
+            .iter()
+            .fold(0u64, |acc, &b| (acc << 8) | b as u64);
+        pos += offset_len;
+        let size = u32::from_be_bytes([idx[pos], idx[pos + 1], idx[pos + 2], idx[pos + 3]]);
+        pos += 4;
+        index.entry(word).or_default().push((offset, size));
+    }
+
+    Ok(Dictionary {
+        name,
+        format: Format::StarDict { sametypesequence },
+        index,
+        data: DictData::open(&dict_path)?,
+    })
+}
+
+/// Split one StarDict article into (type, text) fields. Lowercase types are
+/// NUL-terminated text, uppercase ones are size-prefixed binary (skipped).
+/// With `sametypesequence` the type bytes are omitted and the last field
+/// runs to the end of the article.
+fn stardict_fields(data: &[u8], sametypesequence: &str) -> Vec<(char, String)> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    let types: Vec<char> = sametypesequence.chars().collect();
+    let mut i = 0;
+
+    while pos < data.len() {
+        let (kind, last) = if types.is_empty() {
+            let kind = data[pos] as char;
+            pos += 1;
+            (kind, false)
+        } else if i < types.len() {
+            i += 1;
+            (types[i - 1], i == types.len())
+        } else {
+            break;
+        };
+
+        if kind.is_ascii_lowercase() {
+            let end = if last {
+                data.len()
+            } else {
+                data[pos..].iter().position(|&b| b == 0).map_or(data.len(), |e| pos + e)
+            };
+            fields.push((kind, String::from_utf8_lossy(&data[pos..end]).into_owned()));
+            pos = end + 1;
+        } else if last {
+            break;
+        } else {
+            if pos + 4 > data.len() {
+                break;
+            }
+            let size = u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]);
+            pos += 4 + size as usize;
+        }
+    }
+    fields
+}
+
+// ==================== dictd ====================
+
+fn load_dictd(index_path: &Path) -> Result<Dictionary, String> {
+    let text = fs::read_to_string(index_path).map_err(|e| format!("Read error: {}", e))?;
+    let dict_path = sibling(index_path, &["dict", "dict.dz"]).ok_or("Missing .dict file")?;
+
+    let mut index: HashMap<String, Vec<(u64, u32)>> = HashMap::new();
+    for line in text.lines() {
+        let mut parts = line.split('\t');
+        let (Some(word), Some(offset), Some(size)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        let (Some(offset), Some(size)) = (dictd_b64(offset), dictd_b64(size)) else {
+            continue;
+        };
+        index.entry(word.to_lowercase()).or_default().push((offset, size as u32));
+    }
+
+    let data = DictData::open(&dict_path)?;
+
+    // dictd stores the database name as a pseudo entry: headword line, then the name
+    let name = index
+        .get("00-database-short")
+        .or_else(|| index.get("00databaseshort"))
+        .and_then(|locs| data.read(locs[0].0, locs[0].1))
+        .and_then(|raw| {
+            let text = String::from_utf8_lossy(&raw);
+            text.lines().nth(1).map(|l| l.trim().to_string())
+        })
+        .filter(|n| !n.is_empty())
+        .unwrap_or_else(|| index_path.file_stem().unwrap_or_default().to_string_lossy().into_owned());
+
+    Ok(Dictionary {
+        name,
+        format: Format::Dictd,
+        index,
+        data,
+    })
+}
+
+/// dictd numbers use base64 digits, most significant first
+fn dictd_b64(s: &str) -> Option<u64> {
+    s.bytes().try_fold(0u64, |acc, b| {
+        let v = match b {
+            b'A'..=b'Z' => b - b'A',
+            b'a'..=b'z' => b - b'a' + 26,
+            b'0'..=b'9' => b - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        Some(acc * 64 + v as u64)
+    })
+}
+
+// ==================== Article Parsing ====================
+
+const PARTS_OF_SPEECH: &[&str] = &[
+    "n.", "v.", "vt.", "vi.", "adj.", "a.", "adv.", "ad.", "prep.", "conj.", "pron.", "int.",
+    "interj.", "num.", "art.", "aux.", "abbr.", "pl.", "noun", "verb", "adjective", "adverb",
+    "preposition", "conjunction", "pronoun", "interjection",
+];
+
+/// Drop HTML/XML tags and decode the common entities
+pub fn strip_markup(text: &str) -> String {
+    let text = text
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n");
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Strip list markers like "1.", "2)", "(3)"
+fn strip_numbering(line: &str) -> &str {
+    let trimmed = line.trim_start_matches('(');
+    let digits = trimmed.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        let rest = &trimmed[digits..];
+        if let Some(rest) = rest.strip_prefix('.').or_else(|| rest.strip_prefix(')')) {
+            return rest.trim_start();
+        }
+    }
+    line
+}
+
+/// Best-effort split of a plain-text article into phonetics, parts of speech,
+/// definitions and examples
+fn parse_text(text: &str, entry: &mut DictionaryEntry) {
+    for line in text.lines() {
+        let mut line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        // Leading /fəˈnetɪk/ or [fəˈnetɪk]
+        for (open, close) in [('/', '/'), ('[', ']')] {
+            if let Some(rest) = line.strip_prefix(open) {
+                if let Some(end) = rest.find(close) {
+                    if end > 0 && end < 60 {
+                        entry.phonetics.push(format!("{}{}{}", open, &rest[..end], close));
+                        line = rest[end + close.len_utf8()..].trim();
+                    }
+                }
+            }
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        let lower = line.to_lowercase();
+        if let Some(example) = ["e.g.", "eg:", "例:", "例：", "例句:", "例句："]
+            .iter()
+            .find_map(|p| lower.starts_with(p).then(|| line[p.len()..].trim()))
+        {
+            entry.examples.push(example.to_string());
+            continue;
+        }
+
+        let line = strip_numbering(line);
+        let first = line.split_whitespace().next().unwrap_or("");
+        if PARTS_OF_SPEECH.contains(&first.to_lowercase().as_str()) {
+            entry.meanings.push(Meaning {
+                part_of_speech: first.to_string(),
+                definitions: Vec::new(),
+            });
+            let rest = line[first.len()..].trim();
+            if rest.is_empty() {
+                continue;
+            }
+            push_definitions(entry, rest);
+        } else {
+            push_definitions(entry, line);
+        }
+    }
+}
+
+fn push_definitions(entry: &mut DictionaryEntry, line: &str) {
+    if entry.meanings.is_empty() {
+        entry.meanings.push(Meaning::default());
+    }
+    let meaning = entry.meanings.last_mut().unwrap();
+    meaning.definitions.extend(
+        line.split([';', '；'])
+            .map(|d| d.trim())
+            .filter(|d| !d.is_empty())
+            .map(|d| d.to_string()),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    /// A scratch directory removed again when the test ends
+    struct Fixture(PathBuf);
+
+    impl Fixture {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("easydict-dict-{}-{}", std::process::id(), name));
+            fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, file: &str, data: &[u8]) -> PathBuf {
+            let path = self.0.join(file);
+            fs::write(&path, data).unwrap();
+            path
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            fs::remove_dir_all(&self.0).ok();
+        }
+    }
+
+    /// .idx rows of (headword, offset, size) with `offset_len`-byte offsets
+    fn idx(rows: &[(&str, u64, u32)], offset_len: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        for (word, offset, size) in rows {
+            out.extend_from_slice(word.as_bytes());
+            out.push(0);
+            out.extend_from_slice(&offset.to_be_bytes()[8 - offset_len..]);
+            out.extend_from_slice(&size.to_be_bytes());
+        }
+        out
+    }
+
+    fn ifo(extra: &str) -> String {
+        format!("StarDict's dict ifo file\nversion=2.4.2\nbookname=Test Dict\n{}", extra)
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn looks_up_stardict_with_sametypesequence() {
+        let fixture = Fixture::new("sametype");
+        let apple = "/ˈæp.əl/\nn. 苹果; 苹果树\ne.g. An apple a day.";
+        let run = "vi. 跑";
+        let dict = format!("{}{}", apple, run);
+        let rows = [("Apple", 0, apple.len() as u32), ("run", apple.len() as u64, run.len() as u32)];
+        fixture.write("test.idx", &idx(&rows, 4));
+        fixture.write("test.dict", dict.as_bytes());
+        let ifo_path = fixture.write("test.ifo", ifo("sametypesequence=m\n").as_bytes());
+
+        let dicts = Dictionaries::load(&[ifo_path.to_string_lossy().into_owned()]);
+        let entry = dicts.lookup(" APPLE ").unwrap();
+        assert_eq!(entry.word, "APPLE");
+        assert_eq!(entry.source, "Test Dict");
+        assert_eq!(entry.phonetics, ["/ˈæp.əl/"]);
+        assert_eq!(entry.meanings[0].part_of_speech, "n.");
+        assert_eq!(entry.meanings[0].definitions, ["苹果", "苹果树"]);
+        assert_eq!(entry.examples, ["An apple a day."]);
+        assert_eq!(dicts.lookup("run").unwrap().summary(), "vi. 跑");
+        assert!(dicts.lookup("pear").is_none());
+    }
+
+    #[test]
+    fn looks_up_stardict_with_type_bytes_and_64_bit_offsets() {
+        let fixture = Fixture::new("typed");
+        let mut article = "t[wɜːd]\0W".as_bytes().to_vec();
+        article.extend_from_slice(&3u32.to_be_bytes());
+        article.extend_from_slice(b"\x01\x02\x03");
+        article.extend_from_slice("h<b>n.</b> 词&amp;字\0".as_bytes());
+        fixture.write("typed.idx", &idx(&[("word", 0, article.len() as u32)], 8));
+        fixture.write("typed.dict.dz", &gzip(&article));
+        fixture.write("typed.ifo", ifo("idxoffsetbits=64\n").as_bytes());
+
+        // A directory stands for every dictionary in it
+        let dicts = Dictionaries::load(&[fixture.0.to_string_lossy().into_owned()]);
+        let entry = dicts.lookup("word").unwrap();
+        assert_eq!(entry.phonetics, ["[wɜːd]"]);
+        assert_eq!(entry.summary(), "n. 词&字");
+    }
+
+    #[test]
+    fn splits_stardict_fields() {
+        let mut data = b"mfirst\0P".to_vec();
+        data.extend_from_slice(&2u32.to_be_bytes());
+        data.extend_from_slice(b"\0\0tsecond\0");
+        assert_eq!(
+            stardict_fields(&data, ""),
+            [('m', "first".to_string()), ('t', "second".to_string())]
+        );
+        // The last field of a sametypesequence runs to the end, NULs included
+        assert_eq!(
+            stardict_fields(b"/a/\0meaning\0more", "tm"),
+            [('t', "/a/".to_string()), ('m', "meaning\0more".to_string())]
+        );
+        // A binary size past the end stops the split instead of panicking
+        assert_eq!(stardict_fields(b"mok\0W\xff\xff\xff\xff", ""), [('m', "ok".to_string())]);
+        assert!(stardict_fields(b"W\x00", "").is_empty());
+    }
+
+    #[test]
+    fn corrupt_index_is_rejected_without_reading() {
+        let fixture = Fixture::new("corrupt");
+        fixture.write("bad.dict", b"tiny");
+        fixture.write("bad.idx", &idx(&[("huge", 0, u32::MAX), ("far", u32::MAX as u64, 4)], 4));
+        let good = fixture.write("bad.ifo", ifo("sametypesequence=m\n").as_bytes());
+        let dicts = Dictionaries::load(&[good.to_string_lossy().into_owned()]);
+        assert!(dicts.lookup("huge").is_none());
+        assert!(dicts.lookup("far").is_none());
+
+        // Cut off in the middle of a row
+        let mut truncated = idx(&[("word", 0, 4)], 4);
+        truncated.truncate(truncated.len() - 2);
+        fixture.write("cut.idx", &truncated);
+        fixture.write("cut.dict", b"text");
+        let cut = fixture.write("cut.ifo", ifo("").as_bytes());
+        assert!(load_dictionary(&cut).is_err());
+        assert!(Dictionaries::load(&[cut.to_string_lossy().into_owned()]).dicts.is_empty());
+
+        assert!(DictData::Memory(b"tiny".to_vec()).read(2, u32::MAX).is_none());
+        assert!(DictData::Memory(b"tiny".to_vec()).read(u64::MAX, 1).is_none());
+        assert_eq!(DictData::Memory(b"tiny".to_vec()).read(1, 2).unwrap(), b"in");
+    }
+
+    #[test]
+    fn looks_up_dictd() {
+        let fixture = Fixture::new("dictd");
+        let name = "00-database-short\n     Test dictd\n";
+        let cat = "cat\n  n. 猫; 猫科动物\n";
+        let dict = format!("{}{}", name, cat);
+        let b64 = |n: usize| {
+            const DIGITS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+            let mut out = String::new();
+            let mut n = n;
+            loop {
+                out.insert(0, DIGITS[n % 64] as char);
+                n /= 64;
+                if n == 0 {
+                    return out;
+                }
+            }
+        };
+        let index = format!(
+            "00-database-short\t{}\t{}\ncat\t{}\t{}\nbroken\t!!\tA\n",
+            b64(0),
+            b64(name.len()),
+            b64(name.len()),
+            b64(cat.len())
+        );
+        let index_path = fixture.write("test.index", index.as_bytes());
+        fixture.write("test.dict.dz", &gzip(dict.as_bytes()));
+
+        let dicts = Dictionaries::load(&[index_path.to_string_lossy().into_owned()]);
+        let entry = dicts.lookup("Cat").unwrap();
+        assert_eq!(entry.source, "Test dictd");
+        assert_eq!(entry.summary(), "n. 猫; 猫科动物");
+        assert!(dicts.lookup("broken").is_none());
+    }
+
+    #[test]
+    fn decodes_dictd_numbers() {
+        assert_eq!(dictd_b64("A"), Some(0));
+        assert_eq!(dictd_b64("/"), Some(63));
+        assert_eq!(dictd_b64("BA"), Some(64));
+        assert_eq!(dictd_b64("Ba9"), Some(64 * 64 + 26 * 64 + 61));
+        assert_eq!(dictd_b64("A-"), None);
+    }
+
+    #[test]
+    fn parses_plain_text_articles() {
+        let mut entry = DictionaryEntry::default();
+        parse_text(
+            "[rʌn]\n1. vi. 跑；奔跑\n2) vt. 经营\n(3) n.\n赛跑; 路程\n例句：He runs fast.\nplain note",
+            &mut entry,
+        );
+        assert_eq!(entry.phonetics, ["[rʌn]"]);
+        let parts: Vec<_> = entry.meanings.iter().map(|m| m.part_of_speech.as_str()).collect();
+        assert_eq!(parts, ["vi.", "vt.", "n."]);
+        assert_eq!(entry.meanings[0].definitions, ["跑", "奔跑"]);
+        assert_eq!(entry.meanings[2].definitions, ["赛跑", "路程", "plain note"]);
+        assert_eq!(entry.examples, ["He runs fast."]);
+
+        // Text before any part of speech gets an untitled meaning
+        let mut entry = DictionaryEntry::default();
+        parse_text("a small fruit; a pome", &mut entry);
+        assert_eq!(entry.summary(), "a small fruit; a pome");
+    }
+
+    #[test]
+    fn strips_markup() {
+        assert_eq!(strip_markup("<i>a</i><br/>b &lt;c&gt; &amp;amp;"), "a\nb <c> &amp;");
+    }
+}
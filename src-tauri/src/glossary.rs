@@ -0,0 +1,360 @@
+use crate::config::data_dir;
+use crate::translate::{TranslateRequest, TranslateResult, Translator};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    pub id: u64,
+    /// Language code, or "*" for any
+    pub source_lang: String,
+    /// Language code, or "*" for any
+    pub target_lang: String,
+    pub term: String,
+    /// Fixed translation; empty means the term must stay untranslated
+    pub translation: String,
+}
+
+impl GlossaryEntry {
+    /// What the term must become in the output
+    pub fn output(&self) -> &str {
+        if self.translation.is_empty() {
+            &self.term
+        } else {
+            &self.translation
+        }
+    }
+}
+
+/// User terminology persisted as `glossary.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Glossary {
+    next_id: u64,
+    entries: Vec<GlossaryEntry>,
+}
+
+fn glossary_path() -> PathBuf {
+    data_dir().join("glossary.json")
+}
+
+impl Glossary {
+    pub fn load() -> Self {
+        let data = fs::read_to_string(glossary_path()).unwrap_or_default();
+        serde_json::from_str(&data).unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Ok(data) = serde_json::to_string_pretty(self) {
+            fs::write(glossary_path(), data).ok();
+        }
+    }
+
+    pub fn entries(&self) -> Vec<GlossaryEntry> {
+        self.entries.clone()
+    }
+
+    /// Import CSV or TSV rows of `term[,translation]` for one language pair.
+    /// A term already present for the pair is overwritten. Returns the number of rows imported.
+    pub fn import(&mut self, content: &str, source_lang: &str, target_lang: &str) -> usize {
+        let rows = parse_rows(content);
+        for (term, translation) in &rows {
+            self.entries.retain(|e| {
+                !(e.source_lang == source_lang && e.target_lang == target_lang && e.term == *term)
+            });
+            self.next_id += 1;
+            self.entries.push(GlossaryEntry {
+                id: self.next_id,
+                source_lang: source_lang.to_string(),
+                target_lang: target_lang.to_string(),
+                term: term.clone(),
+                translation: translation.clone(),
+            });
+        }
+        self.save();
+        rows.len()
+    }
+
+    pub fn delete(&mut self, id: u64) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.id != id);
+        let removed = self.entries.len() != before;
+        if removed {
+            self.save();
+        }
+        removed
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.save();
+    }
+
+    /// Entries for this language pair whose term occurs in `text`, longest term first
+    pub fn matching(&self, text: &str, source_lang: &str, target_lang: &str) -> Vec<GlossaryEntry> {
+        let mut found: Vec<GlossaryEntry> = self
+            .entries
+            .iter()
+            .filter(|e| e.source_lang == "*" || e.source_lang == source_lang)
+            .filter(|e| e.target_lang == "*" || e.target_lang == target_lang)
+            .filter(|e| !e.term.is_empty() && text.contains(&e.term))
+            .cloned()
+            .collect();
+        found.sort_by_key(|e| std::cmp::Reverse(e.term.chars().count()));
+        found
+    }
+}
+
+// ==================== Import ====================
+
+/// Parse `term<sep>translation` rows; the separator is a tab if the first row has one,
+/// otherwise a comma with CSV quoting. A header row is skipped.
+fn parse_rows(content: &str) -> Vec<(String, String)> {
+    let content = content.trim_start_matches('\u{feff}');
+    let tab = content
+        .lines()
+        .find(|l| !l.trim().is_empty())
+        .is_some_and(|l| l.contains('\t'));
+
+    let mut rows = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let cells = if tab {
+            line.split('\t').map(|c| c.to_string()).collect()
+        } else {
+            split_csv_line(line)
+        };
+        let term = cells.first().map(|c| c.trim().to_string()).unwrap_or_default();
+        let translation = cells.get(1).map(|c| c.trim().to_string()).unwrap_or_default();
+        if term.is_empty() {
+            continue;
+        }
+        let header = ["term", "source", "源词", "原文", "术语"];
+        if i == 0 && header.contains(&term.to_lowercase().as_str()) {
+            continue;
+        }
+        rows.push((term, translation));
+    }
+    rows
+}
+
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    cells
+}
+
+// ==================== Term Protection ====================
+
+fn is_word_char(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_ascii_alphanumeric())
+}
+
+/// Byte length and number of a `{{N}}` placeholder at the start of `s`. Engines
+/// sometimes add spaces or turn the braces full-width (｛｛0｝｝), both are accepted.
+fn placeholder_at(s: &str) -> Option<(usize, usize)> {
+    let mut rest = s;
+    for _ in 0..2 {
+        rest = rest.strip_prefix(['{', '｛'])?;
+    }
+    rest = rest.trim_start_matches(' ');
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let number = rest[..digits].parse().ok()?;
+    rest = rest[digits..].trim_start_matches(' ');
+    for _ in 0..2 {
+        rest = rest.strip_prefix(['}', '｝'])?;
+    }
+    Some((s.len() - rest.len(), number))
+}
+
+/// Replace glossary terms with `{{N}}` placeholders that MT engines pass through.
+/// Returns the protected text and, per placeholder, what it must be restored to.
+/// Placeholder-like text already in the input gets a placeholder of its own, so
+/// it comes back unchanged.
+pub fn protect(text: &str, entries: &[GlossaryEntry]) -> (String, Vec<String>) {
+    let mut out = String::with_capacity(text.len());
+    let mut outputs = Vec::new();
+    let mut prev: Option<char> = None;
+    let mut rest = text;
+
+    'scan: while let Some(c) = rest.chars().next() {
+        if let Some((len, _)) = placeholder_at(rest) {
+            out.push_str(&format!("{{{{{}}}}}", outputs.len()));
+            outputs.push(rest[..len].to_string());
+            prev = Some('}');
+            rest = &rest[len..];
+            continue;
+        }
+        for entry in entries {
+            if !rest.starts_with(&entry.term) {
+                continue;
+            }
+            // Latin terms only match whole words ("AI" must not hit "CHAIR")
+            let after = rest[entry.term.len()..].chars().next();
+            let starts_word = entry.term.starts_with(|c: char| c.is_ascii_alphanumeric());
+            let ends_word = entry.term.ends_with(|c: char| c.is_ascii_alphanumeric());
+            if (starts_word && is_word_char(prev)) || (ends_word && is_word_char(after)) {
+                continue;
+            }
+            out.push_str(&format!("{{{{{}}}}}", outputs.len()));
+            outputs.push(entry.output().to_string());
+            prev = entry.term.chars().last();
+            rest = &rest[entry.term.len()..];
+            continue 'scan;
+        }
+        out.push(c);
+        prev = Some(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    (out, outputs)
+}
+
+/// Put the glossary outputs back in place of the `{{N}}` placeholders
+pub fn restore(text: &str, outputs: &[String]) -> String {
+    if outputs.is_empty() {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if let Some((len, number)) = placeholder_at(rest) {
+            if let Some(output) = outputs.get(number) {
+                out.push_str(output);
+                rest = &rest[len..];
+                continue;
+            }
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    out
+}
+
+/// Run a translator with the request's glossary enforced: translators that
+/// apply it themselves get the request as is, the rest see protected text.
+/// Errors come back as a failed result for the translator's card.
+pub async fn translate_with_glossary(
+    translator: &dyn Translator,
+    client: &Client,
+    req: &TranslateRequest,
+) -> TranslateResult {
+    let result = if req.glossary.is_empty() || translator.capabilities().glossary {
+        translator.translate(client, req).await
+    } else {
+        let (protected, outputs) = protect(&req.text, &req.glossary);
+        let inner = TranslateRequest {
+            text: protected,
+            ..req.clone()
+        };
+        translator.translate(client, &inner).await.map(|mut result| {
+            result.translated = restore(&result.translated, &outputs);
+            result
+        })
+    };
+    result.unwrap_or_else(|e| TranslateResult::err(translator.name(), req, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(term: &str, translation: &str) -> GlossaryEntry {
+        GlossaryEntry {
+            id: 0,
+            source_lang: "*".to_string(),
+            target_lang: "*".to_string(),
+            term: term.to_string(),
+            translation: translation.to_string(),
+        }
+    }
+
+    #[test]
+    fn protects_and_restores_terms() {
+        let entries = [entry("Tauri", ""), entry("Rust", "Rust 语言")];
+        let (protected, outputs) = protect("Tauri apps are written in Rust.", &entries);
+        assert_eq!(protected, "{{0}} apps are written in {{1}}.");
+        assert_eq!(outputs, ["Tauri", "Rust 语言"]);
+        assert_eq!(restore("{{0}} 应用用 {{1}} 编写。", &outputs), "Tauri 应用用 Rust 语言 编写。");
+    }
+
+    #[test]
+    fn latin_terms_match_whole_words_only() {
+        let entries = [entry("AI", "人工智能")];
+        let (protected, outputs) = protect("AI on a CHAIR, AIs and (AI).", &entries);
+        assert_eq!(protected, "{{0}} on a CHAIR, AIs and ({{1}}).");
+        assert_eq!(outputs, ["人工智能", "人工智能"]);
+    }
+
+    #[test]
+    fn cjk_terms_match_inside_text() {
+        // CJK has no spaces between words, so no boundary is required
+        let entries = [entry("人工智能", "AI"), entry("智能", "smart")];
+        let (protected, outputs) = protect("我喜欢人工智能和智能手机", &entries);
+        assert_eq!(protected, "我喜欢{{0}}和{{1}}手机");
+        assert_eq!(outputs, ["AI", "smart"]);
+    }
+
+    #[test]
+    fn restore_accepts_mangled_placeholders() {
+        let outputs = ["Tauri".to_string(), "Rust".to_string()];
+        assert_eq!(restore("｛｛0｝｝ and {{ 1 }}", &outputs), "Tauri and Rust");
+        assert_eq!(restore("{{0｝｝ and ｛{1}}", &outputs), "Tauri and Rust");
+        // Unknown numbers and broken braces are left as they are
+        assert_eq!(restore("{{7}} {0}} {{x}}", &outputs), "{{7}} {0}} {{x}}");
+    }
+
+    #[test]
+    fn placeholders_in_the_input_survive() {
+        let entries = [entry("Tauri", "陶瑞")];
+        let text = "Tauri templates use {{0}} and ｛｛ 1 ｝｝ literally";
+        let (protected, outputs) = protect(text, &entries);
+        assert_eq!(protected, "{{0}} templates use {{1}} and {{2}} literally");
+        assert_eq!(restore(&protected, &outputs), "陶瑞 templates use {{0}} and ｛｛ 1 ｝｝ literally");
+    }
+
+    #[test]
+    fn parses_csv_with_quotes_and_header() {
+        let rows = parse_rows("\u{feff}term,translation\n\"Hello, world\",\"你好，\"\"世界\"\"\"\n\nTauri\n , skipped\n");
+        assert_eq!(
+            rows,
+            [
+                ("Hello, world".to_string(), "你好，\"世界\"".to_string()),
+                ("Tauri".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_tsv() {
+        let rows = parse_rows("machine learning\t机器学习\nA, B\tA，B\n");
+        assert_eq!(
+            rows,
+            [
+                ("machine learning".to_string(), "机器学习".to_string()),
+                ("A, B".to_string(), "A，B".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn splits_csv_line() {
+        assert_eq!(split_csv_line(r#"a,"b,c","d""e",,"#), ["a", "b,c", "d\"e", "", ""]);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::config::data_dir;
+use crate::translate::TranslateResult;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: u64,
+    /// Unix time in seconds
+    pub timestamp: u64,
+    pub text: String,
+    /// Detected (or user-chosen) source language
+    pub source_lang: String,
+    pub target_lang: String,
+    pub results: Vec<TranslateResult>,
+}
+
+/// Translation history persisted as `history.json`, oldest entry first
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct History {
+    next_id: u64,
+    entries: Vec<HistoryEntry>,
+}
+
+fn history_path() -> PathBuf {
+    data_dir().join("history.json")
+}
+
+impl History {
+    pub fn load() -> Self {
+        let data = fs::read_to_string(history_path()).unwrap_or_default();
+        serde_json::from_str(&data).unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Ok(data) = serde_json::to_string(self) {
+            fs::write(history_path(), data).ok();
+        }
+    }
+
+    /// Record a finished translation. Translating the same text to the same
+    /// target again moves it to the top instead of adding a duplicate.
+    pub fn record(
+        &mut self,
+        text: &str,
+        source_lang: &str,
+        target_lang: &str,
+        results: Vec<TranslateResult>,
+        limit: usize,
+    ) {
+        if limit == 0 || text.trim().is_empty() {
+            return;
+        }
+        // Nothing worth keeping if every service failed
+        if results.iter().all(|r| r.error.is_some()) {
+            return;
+        }
+
+        self.entries
+            .retain(|e| !(e.text == text && e.target_lang == target_lang));
+
+        self.next_id += 1;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.entries.push(HistoryEntry {
+            id: self.next_id,
+            timestamp,
+            text: text.to_string(),
+            source_lang: source_lang.to_string(),
+            target_lang: target_lang.to_string(),
+            results,
+        });
+
+        self.truncate(limit);
+        self.save();
+    }
+
+    /// Newest first, paginated
+    pub fn list(&self, offset: usize, limit: usize) -> Vec<HistoryEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .skip(offset)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Case-insensitive match on the source text and every translation, newest first
+    pub fn search(&self, query: &str, limit: usize) -> Vec<HistoryEntry> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return self.list(0, limit);
+        }
+        self.entries
+            .iter()
+            .rev()
+            .filter(|e| {
+                e.text.to_lowercase().contains(&query)
+                    || e.results
+                        .iter()
+                        .any(|r| r.translated.to_lowercase().contains(&query))
+            })
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    pub fn delete(&mut self, id: u64) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|e| e.id != id);
+        let removed = self.entries.len() != before;
+        if removed {
+            self.save();
+        }
+        removed
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.save();
+    }
+
+    /// Apply a (possibly lowered) retention limit
+    pub fn set_limit(&mut self, limit: usize) {
+        if self.entries.len() > limit {
+            self.truncate(limit);
+            self.save();
+        }
+    }
+
+    fn truncate(&mut self, limit: usize) {
+        if self.entries.len() > limit {
+            let excess = self.entries.len() - limit;
+            self.entries.drain(..excess);
+        }
+    }
+}
@@ -1,10 +1,21 @@
+mod cache;
 mod config;
+mod dictionary;
+mod glossary;
+mod history;
 mod ocr;
 mod translate;
 mod tts;
+mod usage;
 
+use cache::{cache_key, ResultCache};
 use config::{AppConfig, load_config, save_config};
+use dictionary::{DictionaryEntry, Dictionaries};
+use glossary::{Glossary, GlossaryEntry};
+use history::{History, HistoryEntry};
 use reqwest::Client;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::sync::Mutex;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -14,6 +25,8 @@ use tauri::{
     tray::TrayIconBuilder,
     WebviewWindowBuilder, WebviewUrl,
 };
+use tokio::task::JoinSet;
+use usage::{ServiceUsage, Usage};
 
 pub struct ScreenshotData {
     pub rgba: Vec<u8>,
@@ -24,6 +37,11 @@ pub struct ScreenshotData {
 pub struct AppState {
     pub config: Mutex<AppConfig>,
     pub client: Client,
+    pub history: Mutex<History>,
+    pub cache: Mutex<ResultCache>,
+    pub dictionaries: Mutex<Dictionaries>,
+    pub glossary: Mutex<Glossary>,
+    pub usage: Mutex<Usage>,
     pub clipboard_monitoring: Arc<AtomicBool>,
     pub screenshot_data: Mutex<Option<ScreenshotData>>,
     pub screenshot_in_progress: AtomicBool,
@@ -37,115 +55,608 @@ fn get_config(state: tauri::State<AppState>) -> AppConfig {
 }
 
 #[tauri::command]
-fn update_config(state: tauri::State<AppState>, config: AppConfig) {
+fn update_config(app: AppHandle, state: tauri::State<AppState>, config: AppConfig) {
     state.clipboard_monitoring.store(
         config.select_translate.enabled && config.select_translate.monitor_clipboard,
         Ordering::Relaxed,
     );
+    state.history.lock().unwrap().set_limit(config.general.history_limit);
     save_config(&config);
-    *state.config.lock().unwrap() = config;
+    let (dictionaries_changed, libre_changed) = {
+        let mut current = state.config.lock().unwrap();
+        let changed = (
+            current.dictionary.paths != config.dictionary.paths,
+            current.services.libre != config.services.libre,
+        );
+        *current = config;
+        changed
+    };
+    if dictionaries_changed {
+        load_dictionaries(&app);
+    }
+    if libre_changed {
+        load_libre_languages(&app);
+    }
 }
 
 #[tauri::command]
-fn get_languages() -> Vec<translate::LangPair> {
+fn get_languages() -> Vec<translate::Language> {
     translate::supported_languages()
 }
 
 #[tauri::command]
-fn detect_language(text: String) -> String {
-    translate::detect_language(&text)
+fn get_services(state: tauri::State<AppState>) -> Vec<translate::ServiceInfo> {
+    let config = state.config.lock().unwrap().clone();
+    translate::service_infos(&config.services)
+}
+
+#[tauri::command]
+fn get_prompt_presets() -> Vec<translate::PromptPreset> {
+    translate::prompt_presets()
+}
+
+#[tauri::command]
+fn detect_language(text: String) -> translate::Detection {
+    translate::detect(&text)
+}
+
+/// Local detection at or above this confidence is passed to the services as
+/// the source language; below it they detect the language themselves
+const TRUSTED_DETECTION: f64 = 0.8;
+
+/// Resolve "auto" source/target and the mode's prompt template into a
+/// concrete request for the translators
+fn prepare_request(
+    config: &AppConfig,
+    text: String,
+    source: String,
+    target: String,
+    mode: translate::PromptMode,
+    context: String,
+) -> translate::TranslateRequest {
+    let (source, actual_source) = if source == "auto" {
+        let detection = translate::detect(&text);
+        let lang = detection.lang.to_string();
+        if detection.confidence >= TRUSTED_DETECTION {
+            (lang.clone(), lang)
+        } else {
+            (source, lang)
+        }
+    } else {
+        (source.clone(), source)
+    };
+
+    let actual_target = if target == "auto" {
+        translate::route_target(
+            &actual_source,
+            &config.general.target_rules,
+            &config.general.first_lang,
+            &config.general.second_lang,
+            &translate::enabled_languages(&config.services),
+        )
+    } else {
+        target
+    };
+
+    translate::TranslateRequest {
+        text,
+        source,
+        detected: actual_source,
+        target: actual_target,
+        on_delta: None,
+        glossary: Vec::new(),
+        mode,
+        prompt: translate::resolve_template(mode, &config.prompts),
+        context,
+    }
+}
+
+/// Cache key for one service's result; other modes and any context give
+/// different output than a plain translation, so they are keyed apart
+fn result_cache_key(service: &str, request: &translate::TranslateRequest) -> String {
+    let service = match (request.mode, request.context.is_empty()) {
+        (translate::PromptMode::Translate, true) => service.to_string(),
+        (mode, _) => format!("{}#{}#{}", service, mode.id(), request.context),
+    };
+    cache_key(&service, &request.source, &request.target, &request.text)
+}
+
+fn record_history(
+    state: &AppState,
+    config: &AppConfig,
+    request: &translate::TranslateRequest,
+    results: Vec<translate::TranslateResult>,
+) {
+    state.history.lock().unwrap().record(
+        &request.text,
+        &request.detected,
+        &request.target,
+        results,
+        config.general.history_limit,
+    );
+}
+
+/// Provider tasks for one request, plus results available without a network
+/// call (cache hits and the offline dictionary)
+struct Dispatch {
+    ready: Vec<translate::TranslateResult>,
+    tasks: JoinSet<translate::TranslateResult>,
+    names: HashMap<tokio::task::Id, String>,
+    /// Service name -> instance key, for usage accounting
+    keys: HashMap<String, String>,
+    /// Service names in registry order
+    order: Vec<String>,
+}
+
+impl Dispatch {
+    /// Next provider result in completion order; a panicked task becomes an error result
+    async fn next(
+        &mut self,
+        request: &translate::TranslateRequest,
+    ) -> Option<translate::TranslateResult> {
+        let joined = self.tasks.join_next_with_id().await?;
+        Some(match joined {
+            Ok((_, result)) => result,
+            Err(e) => translate::TranslateResult::err(
+                self.names.remove(&e.id()).unwrap_or_default(),
+                request,
+                translate::TranslateError::Internal {
+                    message: format!("Task error: {}", e),
+                },
+            ),
+        })
+    }
+}
+
+/// Look up the offline dictionary and every enabled service in the cache,
+/// then spawn provider tasks (with the matching glossary entries) for the misses
+fn dispatch(
+    state: &AppState,
+    config: &AppConfig,
+    request: &translate::TranslateRequest,
+    use_cache: bool,
+) -> Dispatch {
+    let mut request = request.clone();
+    request.glossary = state.glossary.lock().unwrap().matching(
+        &request.text,
+        &request.detected,
+        &request.target,
+    );
+    let ttl_secs = config.cache.ttl_hours * 3600;
+    let mut cache = state.cache.lock().unwrap();
+    let mut d = Dispatch {
+        ready: Vec::new(),
+        tasks: JoinSet::new(),
+        names: HashMap::new(),
+        keys: HashMap::new(),
+        order: Vec::new(),
+    };
+
+    let plain = request.mode == translate::PromptMode::Translate;
+    if plain && config.dictionary.enabled && dictionary::looks_like_word(&request.text) {
+        if let Some(entry) = state.dictionaries.lock().unwrap().lookup(&request.text) {
+            let mut result = translate::TranslateResult::ok(
+                "Dictionary",
+                entry.summary(),
+                request.detected.clone(),
+                &request.target,
+            );
+            result.dictionary = Some(entry);
+            d.order.push(result.service.clone());
+            d.ready.push(result);
+        }
+    }
+
+    for translator in translate::build_registry(&config.services) {
+        if !plain && !translator.capabilities().prompt_modes {
+            continue;
+        }
+        let name = translator.name();
+        d.order.push(name.clone());
+        d.keys.insert(name.clone(), translator.instance_key());
+        if use_cache {
+            let key = result_cache_key(&name, &request);
+            if let Some(hit) = cache.get(&key, ttl_secs) {
+                d.ready.push(hit);
+                continue;
+            }
+        }
+        let c = state.client.clone();
+        let req = request.clone();
+        let handle = d.tasks.spawn(async move {
+            glossary::translate_with_glossary(translator.as_ref(), &c, &req).await
+        });
+        d.names.insert(handle.id(), name);
+    }
+    d
+}
+
+fn store_in_cache(
+    state: &AppState,
+    config: &AppConfig,
+    request: &translate::TranslateRequest,
+    fresh: &[translate::TranslateResult],
+) {
+    if !config.cache.enabled {
+        return;
+    }
+    let mut cache = state.cache.lock().unwrap();
+    for r in fresh {
+        let key = result_cache_key(&r.service, request);
+        cache.insert(key, r.clone(), config.cache.max_entries);
+    }
+    cache.save();
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn translate_text(
+    app: AppHandle,
     state: tauri::State<'_, AppState>,
     text: String,
     source: String,
     target: String,
+    bypass_cache: Option<bool>,
+    mode: Option<translate::PromptMode>,
+    context: Option<String>,
 ) -> Result<Vec<translate::TranslateResult>, String> {
     let config = state.config.lock().unwrap().clone();
-    let client = &state.client;
+    let mode = mode.unwrap_or_default();
+    let request = prepare_request(&config, text, source, target, mode, context.unwrap_or_default());
+    let use_cache = config.cache.enabled && !bypass_cache.unwrap_or(false);
+
+    let mut d = dispatch(&state, &config, &request, use_cache);
+    let mut fresh = Vec::new();
+    while let Some(result) = d.next(&request).await {
+        fresh.push(result);
+    }
+    store_in_cache(&state, &config, &request, &fresh);
+    record_usage(&app, &d, &request, &fresh);
 
-    let actual_source = if source == "auto" {
-        translate::detect_language(&text)
-    } else {
-        source.clone()
+    let mut results = std::mem::take(&mut d.ready);
+    results.extend(fresh);
+    results.sort_by_key(|r| d.order.iter().position(|name| *name == r.service));
+
+    record_history(&state, &config, &request, results.clone());
+    Ok(results)
+}
+
+#[derive(Clone, Serialize)]
+struct TranslatePartial {
+    request_id: u32,
+    result: translate::TranslateResult,
+}
+
+#[derive(Clone, Serialize)]
+struct TranslateDelta {
+    request_id: u32,
+    service: String,
+    delta: String,
+}
+
+#[derive(Clone, Serialize)]
+struct TranslateDone {
+    request_id: u32,
+    count: usize,
+}
+
+/// Streaming variant of `translate_text`: every service result is emitted as a
+/// `translate-partial` event as soon as it finishes, followed by `translate-done`.
+/// Streaming-capable services also emit `translate-delta` events while running.
+/// `request_id` comes from the frontend so it can drop events from stale requests.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn translate_text_stream(
+    app: AppHandle,
+    state: tauri::State<'_, AppState>,
+    request_id: u32,
+    text: String,
+    source: String,
+    target: String,
+    bypass_cache: Option<bool>,
+    mode: Option<translate::PromptMode>,
+    context: Option<String>,
+) -> Result<(), String> {
+    let config = state.config.lock().unwrap().clone();
+    let mode = mode.unwrap_or_default();
+    let mut request = prepare_request(&config, text, source, target, mode, context.unwrap_or_default());
+    let use_cache = config.cache.enabled && !bypass_cache.unwrap_or(false);
+
+    let delta_app = app.clone();
+    request.on_delta = Some(Arc::new(move |d: translate::StreamDelta| {
+        let payload = TranslateDelta {
+            request_id,
+            service: d.service,
+            delta: d.delta,
+        };
+        delta_app.emit("translate-delta", payload).ok();
+    }));
+
+    let emit_partial = |result: &translate::TranslateResult| {
+        let payload = TranslatePartial {
+            request_id,
+            result: result.clone(),
+        };
+        app.emit("translate-partial", payload).ok();
     };
 
-    let actual_target = if target == "auto" {
-        translate::auto_target_lang(&actual_source, &config.general.target_lang)
-    } else {
-        target.clone()
+    let mut d = dispatch(&state, &config, &request, use_cache);
+    for result in &d.ready {
+        emit_partial(result);
+    }
+
+    let mut fresh = Vec::new();
+    while let Some(result) = d.next(&request).await {
+        emit_partial(&result);
+        fresh.push(result);
+    }
+    store_in_cache(&state, &config, &request, &fresh);
+    record_usage(&app, &d, &request, &fresh);
+
+    let mut results = std::mem::take(&mut d.ready);
+    results.extend(fresh);
+    let count = results.len();
+    record_history(&state, &config, &request, results);
+    app.emit("translate-done", TranslateDone { request_id, count }).ok();
+    Ok(())
+}
+
+/// Drop every cached translation
+#[tauri::command]
+fn clear_cache(state: tauri::State<AppState>) {
+    state.cache.lock().unwrap().clear();
+}
+
+// ==================== Usage ====================
+
+#[derive(Clone, Serialize)]
+struct UsageThreshold {
+    service: String,
+    key: String,
+    used: u64,
+    limit: u64,
+    percent: u64,
+    disabled: bool,
+}
+
+/// One row of `get_usage`: this month's usage of an enabled service
+#[derive(Serialize)]
+struct UsageRow {
+    key: String,
+    name: String,
+    month: String,
+    characters: u64,
+    requests: u64,
+    provider_count: Option<u64>,
+    provider_limit: Option<u64>,
+    /// Configured limit, else the provider's
+    limit: Option<u64>,
+    percent: Option<u64>,
+}
+
+fn usage_limit(config: &AppConfig, key: &str, usage: &ServiceUsage) -> Option<u64> {
+    config.usage.limits.get(key).copied().or(usage.provider_limit).filter(|&l| l > 0)
+}
+
+/// Count the characters of every successful fresh result against its service
+fn record_usage(
+    app: &AppHandle,
+    d: &Dispatch,
+    request: &translate::TranslateRequest,
+    fresh: &[translate::TranslateResult],
+) {
+    let characters = request.text.chars().count() as u64;
+    for r in fresh.iter().filter(|r| r.error.is_none()) {
+        let Some(key) = d.keys.get(&r.service) else {
+            continue;
+        };
+        let usage = app.state::<AppState>().usage.lock().unwrap().record(key, characters);
+        check_usage(app, key, &r.service, &usage);
+    }
+}
+
+/// Warn once a month when a service passes `warn_percent` of its limit, and
+/// switch it off at the limit when `auto_disable` is set
+fn check_usage(app: &AppHandle, key: &str, name: &str, usage: &ServiceUsage) {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().unwrap().clone();
+    let Some(limit) = usage_limit(&config, key, usage) else {
+        return;
     };
+    let used = usage.used();
+    let percent = used * 100 / limit;
+    let exhausted = used >= limit;
+    if percent < config.usage.warn_percent as u64 || (usage.warned && !exhausted) {
+        return;
+    }
 
-    let mut handles: Vec<tokio::task::JoinHandle<translate::TranslateResult>> = Vec::new();
-
-    if config.services.google.enabled {
-        let c = client.clone();
-        let t = text.clone();
-        let s = source.clone();
-        let tgt = actual_target.clone();
-        handles.push(tokio::spawn(async move {
-            translate::google_translate(&c, &t, &s, &tgt).await
-        }));
-    }
-    if config.services.bing.enabled {
-        let c = client.clone();
-        let t = text.clone();
-        let s = source.clone();
-        let tgt = actual_target.clone();
-        handles.push(tokio::spawn(async move {
-            translate::bing_translate(&c, &t, &s, &tgt).await
-        }));
-    }
-    if config.services.deepl.enabled {
-        let c = client.clone();
-        let t = text.clone();
-        let s = source.clone();
-        let tgt = actual_target.clone();
-        let key = config.services.deepl.api_key.clone();
-        handles.push(tokio::spawn(async move {
-            translate::deepl_translate(&c, &t, &s, &tgt, &key).await
-        }));
-    }
-    if config.services.baidu.enabled {
-        let c = client.clone();
-        let t = text.clone();
-        let s = source.clone();
-        let tgt = actual_target.clone();
-        let app_id = config.services.baidu.app_id.clone();
-        let secret = config.services.baidu.secret_key.clone();
-        handles.push(tokio::spawn(async move {
-            translate::baidu_translate(&c, &t, &s, &tgt, &app_id, &secret).await
-        }));
-    }
-    if config.services.openai.enabled {
-        let c = client.clone();
-        let t = text.clone();
-        let s = source.clone();
-        let tgt = actual_target.clone();
-        let key = config.services.openai.api_key.clone();
-        let url = config.services.openai.api_url.clone();
-        let model = config.services.openai.model.clone();
-        handles.push(tokio::spawn(async move {
-            translate::openai_translate(&c, &t, &s, &tgt, &key, &url, &model).await
-        }));
-    }
-
-    let mut results = Vec::new();
-    for handle in handles {
-        match handle.await {
-            Ok(result) => results.push(result),
-            Err(e) => results.push(translate::TranslateResult {
-                service: "Unknown".to_string(),
-                translated: String::new(),
-                source_lang: source.clone(),
-                target_lang: actual_target.clone(),
-                error: Some(format!("Task error: {}", e)),
-            }),
+    let disabled = exhausted && config.usage.auto_disable && {
+        let mut current = state.config.lock().unwrap();
+        let changed = current.services.set_enabled(key, false);
+        if changed {
+            save_config(&current);
         }
+        changed
+    };
+    if usage.warned && !disabled {
+        return;
     }
-    Ok(results)
+    state.usage.lock().unwrap().mark_warned(key);
+    eprintln!(
+        "[Usage] {} at {}% of {} characters{}",
+        name,
+        percent,
+        limit,
+        if disabled { ", disabled" } else { "" }
+    );
+    let payload = UsageThreshold {
+        service: name.to_string(),
+        key: key.to_string(),
+        used,
+        limit,
+        percent,
+        disabled,
+    };
+    app.emit("usage-threshold", payload).ok();
+}
+
+/// Fetch DeepL's own count for the billing period when a key is set
+async fn refresh_deepl_usage(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let entry = state.config.lock().unwrap().services.deepl.clone();
+    if !entry.enabled || entry.api_key.trim().is_empty() {
+        return;
+    }
+    match translate::load_deepl_usage(&state.client, &entry).await {
+        Ok((count, limit)) => {
+            let usage = state.usage.lock().unwrap().set_reported("deepl", count, limit);
+            check_usage(app, "deepl", "DeepL", &usage);
+        }
+        Err(e) => eprintln!("[Usage] Failed to load DeepL usage: {}", e),
+    }
+}
+
+/// Poll provider-side usage in the background
+fn start_usage_poller(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            refresh_deepl_usage(&app).await;
+            tokio::time::sleep(std::time::Duration::from_secs(15 * 60)).await;
+        }
+    });
+}
+
+/// This month's usage of every enabled service, DeepL refreshed first
+#[tauri::command]
+async fn get_usage(app: AppHandle, state: tauri::State<'_, AppState>) -> Result<Vec<UsageRow>, String> {
+    refresh_deepl_usage(&app).await;
+    let config = state.config.lock().unwrap().clone();
+    let month = usage::current_month();
+    let usage = state.usage.lock().unwrap().month(&month);
+    let rows = translate::service_infos(&config.services)
+        .into_iter()
+        .filter(|info| info.enabled)
+        .map(|info| {
+            let entry = usage.get(&info.key).cloned().unwrap_or_default();
+            let limit = usage_limit(&config, &info.key, &entry);
+            UsageRow {
+                percent: limit.map(|l| entry.used() * 100 / l),
+                limit,
+                month: month.clone(),
+                characters: entry.characters,
+                requests: entry.requests,
+                provider_count: entry.provider_count,
+                provider_limit: entry.provider_limit,
+                key: info.key,
+                name: info.name,
+            }
+        })
+        .collect();
+    Ok(rows)
+}
+
+// ==================== Dictionary ====================
+
+/// (Re)load the configured offline dictionaries off the main thread
+fn load_dictionaries(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let state = app.state::<AppState>();
+        let paths = state.config.lock().unwrap().dictionary.paths.clone();
+        let dictionaries = Dictionaries::load(&paths);
+        *state.dictionaries.lock().unwrap() = dictionaries;
+    });
+}
+
+/// Ask the LibreTranslate server which languages it has, in the background
+fn load_libre_languages(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let entry = state.config.lock().unwrap().services.libre.clone();
+        if !entry.enabled {
+            return;
+        }
+        match translate::load_libre_languages(&state.client, &entry).await {
+            Ok(count) => eprintln!("[LibreTranslate] {} languages at {}", count, entry.base_url),
+            Err(e) => eprintln!("[LibreTranslate] Failed to load languages: {}", e),
+        }
+    });
+}
+
+#[tauri::command]
+fn lookup_word(state: tauri::State<AppState>, word: String) -> Option<DictionaryEntry> {
+    state.dictionaries.lock().unwrap().lookup(&word)
+}
+
+// ==================== Glossary ====================
+
+#[tauri::command]
+fn get_glossary(state: tauri::State<AppState>) -> Vec<GlossaryEntry> {
+    state.glossary.lock().unwrap().entries()
+}
+
+/// Drop cached results, which were translated with the old glossary
+fn glossary_changed(state: &AppState) {
+    state.cache.lock().unwrap().clear();
+}
+
+/// Import CSV/TSV `term,translation` rows for a language pair ("*" matches any)
+#[tauri::command]
+fn import_glossary(
+    state: tauri::State<AppState>,
+    content: String,
+    source_lang: String,
+    target_lang: String,
+) -> usize {
+    let imported = state
+        .glossary
+        .lock()
+        .unwrap()
+        .import(&content, &source_lang, &target_lang);
+    if imported > 0 {
+        glossary_changed(&state);
+    }
+    imported
+}
+
+#[tauri::command]
+fn delete_glossary_entry(state: tauri::State<AppState>, id: u64) -> bool {
+    let deleted = state.glossary.lock().unwrap().delete(id);
+    if deleted {
+        glossary_changed(&state);
+    }
+    deleted
+}
+
+#[tauri::command]
+fn clear_glossary(state: tauri::State<AppState>) {
+    state.glossary.lock().unwrap().clear();
+    glossary_changed(&state);
+}
+
+// ==================== History Commands ====================
+
+/// Newest first
+#[tauri::command]
+fn get_history(state: tauri::State<AppState>, offset: usize, limit: usize) -> Vec<HistoryEntry> {
+    state.history.lock().unwrap().list(offset, limit)
+}
+
+#[tauri::command]
+fn search_history(state: tauri::State<AppState>, query: String, limit: usize) -> Vec<HistoryEntry> {
+    state.history.lock().unwrap().search(&query, limit)
+}
+
+#[tauri::command]
+fn delete_history(state: tauri::State<AppState>, id: u64) -> bool {
+    state.history.lock().unwrap().delete(id)
+}
+
+#[tauri::command]
+fn clear_history(state: tauri::State<AppState>) {
+    state.history.lock().unwrap().clear();
 }
 
 #[tauri::command]
@@ -757,13 +1268,22 @@ fn create_screenshot_window(app: &AppHandle) {
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     let config = load_config();
-    let client = Client::new();
+    // Per-request timeouts come from each service's policy; this only bounds a dead connect
+    let client = Client::builder()
+        .connect_timeout(std::time::Duration::from_secs(10))
+        .build()
+        .unwrap_or_default();
     let monitoring = Arc::new(AtomicBool::new(
         config.select_translate.enabled && config.select_translate.monitor_clipboard,
     ));
     let state = AppState {
         config: Mutex::new(config),
         client,
+        history: Mutex::new(History::load()),
+        cache: Mutex::new(ResultCache::load()),
+        dictionaries: Mutex::new(Dictionaries::default()),
+        glossary: Mutex::new(Glossary::load()),
+        usage: Mutex::new(Usage::load()),
         clipboard_monitoring: monitoring.clone(),
         screenshot_data: Mutex::new(None),
         screenshot_in_progress: AtomicBool::new(false),
@@ -778,8 +1298,22 @@ pub fn run() {
             get_config,
             update_config,
             get_languages,
+            get_services,
+            get_prompt_presets,
             detect_language,
             translate_text,
+            translate_text_stream,
+            clear_cache,
+            get_usage,
+            lookup_word,
+            get_glossary,
+            import_glossary,
+            delete_glossary_entry,
+            clear_glossary,
+            get_history,
+            search_history,
+            delete_history,
+            clear_history,
             speak,
             get_clipboard_text,
             float_icon_clicked,
@@ -794,6 +1328,9 @@ pub fn run() {
             setup_tray(&handle)?;
             setup_shortcuts(&handle)?;
             start_select_monitor(&handle, monitoring.clone());
+            load_dictionaries(&handle);
+            load_libre_languages(&handle);
+            start_usage_poller(&handle);
             // Pre-create the screenshot window for instant activation
             create_screenshot_window(&handle);
             Ok(())
@@ -0,0 +1,288 @@
+use super::sse::SseParser;
+use super::{
+    check_status, langmap, prompt, Capabilities, DeltaSink, StreamDelta, TranslateError, TranslateRequest,
+    TranslateResult, Translator,
+};
+use crate::config::LlmServiceEntry;
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reqwest::{Client, Response};
+
+// ==================== Anthropic Messages ====================
+
+const API_VERSION: &str = "2023-06-01";
+
+pub struct AnthropicTranslator {
+    entry: LlmServiceEntry,
+}
+
+/// An `error` object from a response body or stream event
+fn api_error(error: &serde_json::Value) -> TranslateError {
+    let kind = error["type"].as_str().unwrap_or("");
+    let message = error["message"].as_str().unwrap_or(kind).to_string();
+    match kind {
+        "authentication_error" | "permission_error" => TranslateError::Auth { message },
+        "rate_limit_error" => TranslateError::RateLimit { retry_after: None },
+        "overloaded_error" => TranslateError::Http { status: 529, message },
+        "api_error" => TranslateError::Http { status: 500, message },
+        _ => TranslateError::Provider {
+            code: kind.to_string(),
+            message,
+        },
+    }
+}
+
+impl AnthropicTranslator {
+    pub fn new(entry: &LlmServiceEntry) -> Self {
+        Self { entry: entry.clone() }
+    }
+
+    /// Read a `stream: true` message, forwarding each text delta
+    async fn read_stream(&self, resp: Response, sink: &DeltaSink) -> Result<String, TranslateError> {
+        let mut body = resp.bytes_stream();
+        let mut parser = SseParser::default();
+        let mut translated = String::new();
+
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk?;
+            for data in parser.feed(&chunk) {
+                let json: serde_json::Value = serde_json::from_str(&data)?;
+                match json["type"].as_str() {
+                    Some("message_stop") => return Ok(translated),
+                    Some("error") => return Err(api_error(&json["error"])),
+                    Some("content_block_delta") => {
+                        let Some(delta) = json["delta"]["text"].as_str() else {
+                            continue;
+                        };
+                        translated.push_str(delta);
+                        sink(StreamDelta {
+                            service: self.name(),
+                            delta: delta.to_string(),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(translated)
+    }
+}
+
+#[async_trait]
+impl Translator for AnthropicTranslator {
+    fn id(&self) -> &'static str {
+        "anthropic"
+    }
+
+    fn name(&self) -> String {
+        format!("Anthropic ({})", self.entry.model)
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_credentials: true,
+            detects_language: false,
+            streaming: true,
+            dictionary: false,
+            glossary: true,
+            max_chars: 2000,
+            max_batch: 1,
+            prompt_modes: true,
+        }
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        let entry = &self.entry;
+        if entry.api_key.is_empty() || entry.model.is_empty() {
+            return Err(TranslateError::NotConfigured);
+        }
+
+        let (source, target_desc) = langmap::map_pair(self.id(), req)?;
+        let source_desc = source.unwrap_or("auto-detected language");
+        let (system_prompt, user_prompt) = prompt::render(&req.prompt, source_desc, target_desc, req);
+
+        let body = serde_json::json!({
+            "model": entry.model,
+            "system": system_prompt,
+            "messages": [{"role": "user", "content": user_prompt}],
+            "temperature": entry.temperature,
+            "max_tokens": entry.max_tokens,
+            "stream": req.on_delta.is_some()
+        });
+
+        let resp = client
+            .post(&entry.api_url)
+            .header("x-api-key", &entry.api_key)
+            .header("anthropic-version", API_VERSION)
+            .json(&body)
+            .send()
+            .await?;
+        let resp = check_status(resp).await?;
+
+        if let Some(sink) = &req.on_delta {
+            let translated = self.read_stream(resp, sink).await?;
+            return Ok(TranslateResult::ok(
+                self.name(),
+                translated.trim().to_string(),
+                req.source.clone(),
+                &req.target,
+            ));
+        }
+
+        let json = resp.json::<serde_json::Value>().await?;
+        if json["type"] == "error" {
+            return Err(api_error(&json["error"]));
+        }
+        let blocks = json["content"].as_array().ok_or_else(|| TranslateError::Parse {
+            message: "missing content".to_string(),
+        })?;
+        let translated: String = blocks
+            .iter()
+            .filter(|b| b["type"] == "text")
+            .filter_map(|b| b["text"].as_str())
+            .collect();
+        Ok(TranslateResult::ok(
+            self.name(),
+            translated.trim().to_string(),
+            req.source.clone(),
+            &req.target,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock::{self, MockServer, Reply};
+
+    fn entry(server: &MockServer) -> LlmServiceEntry {
+        LlmServiceEntry {
+            enabled: true,
+            api_key: "sk-ant-test".to_string(),
+            api_url: format!("{}/v1/messages", server.url),
+            model: "test-model".to_string(),
+            temperature: 0.3,
+            max_tokens: 1024,
+        }
+    }
+
+    #[tokio::test]
+    async fn joins_text_blocks() {
+        let server = MockServer::start(|_| {
+            Reply::json(serde_json::json!({
+                "type": "message",
+                "content": [
+                    {"type": "text", "text": " 你好，"},
+                    {"type": "tool_use", "id": "x"},
+                    {"type": "text", "text": "世界 "}
+                ]
+            }))
+        })
+        .await;
+
+        let req = mock::request("Hello, world", "en", "zh-CN");
+        let result = AnthropicTranslator::new(&entry(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好，世界");
+        let sent = &server.requests()[0];
+        assert_eq!(sent.path, "/v1/messages");
+        assert_eq!(sent.header("x-api-key"), Some("sk-ant-test"));
+        assert_eq!(sent.header("anthropic-version"), Some(API_VERSION));
+        assert_eq!(sent.json()["model"], "test-model");
+        assert_eq!(sent.json()["max_tokens"], 1024);
+        assert_eq!(sent.json()["stream"], false);
+        assert!(sent.json()["system"].is_string());
+    }
+
+    #[tokio::test]
+    async fn streams_text_deltas_until_message_stop() {
+        let body = concat!(
+            "event: message_start\n",
+            "data: {\"type\":\"message_start\",\"message\":{}}\n\n",
+            "event: content_block_start\n",
+            "data: {\"type\":\"content_block_start\",\"index\":0}\n\n",
+            "event: ping\n",
+            "data: {\"type\":\"ping\"}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"你好\"}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"input_json_delta\",\"partial_json\":\"{}\"}}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"，世界\"}}\n\n",
+            "event: message_stop\n",
+            "data: {\"type\":\"message_stop\"}\n\n",
+            "event: content_block_delta\n",
+            "data: {\"type\":\"content_block_delta\",\"delta\":{\"type\":\"text_delta\",\"text\":\"ignored\"}}\n\n",
+        )
+        .as_bytes();
+        let server = MockServer::start(move |_| Reply::sse(&[&body[..100], &body[100..]])).await;
+
+        let (req, deltas) = mock::streaming_request("Hello, world", "en", "zh-CN");
+        let result = AnthropicTranslator::new(&entry(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好，世界");
+        assert_eq!(*deltas.lock().unwrap(), ["你好", "，世界"]);
+        assert_eq!(server.requests()[0].json()["stream"], true);
+    }
+
+    #[tokio::test]
+    async fn error_event_in_stream() {
+        let server = MockServer::start(|_| {
+            Reply::sse(&[concat!(
+                "event: error\n",
+                "data: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}}\n\n"
+            )
+            .as_bytes()])
+        })
+        .await;
+
+        let (req, _) = mock::streaming_request("Hello", "en", "zh-CN");
+        let err = AnthropicTranslator::new(&entry(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, TranslateError::Http { status: 529, .. }));
+    }
+
+    #[tokio::test]
+    async fn rejected_key_is_an_auth_error() {
+        let server = MockServer::start(|_| {
+            Reply::status(
+                401,
+                serde_json::json!({
+                    "type": "error",
+                    "error": {"type": "authentication_error", "message": "invalid x-api-key"}
+                }),
+            )
+        })
+        .await;
+
+        let req = mock::request("Hello", "en", "zh-CN");
+        let err = AnthropicTranslator::new(&entry(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, TranslateError::Auth { ref message } if message == "invalid x-api-key"));
+    }
+
+    #[test]
+    fn maps_error_types() {
+        let error = |kind: &str| api_error(&serde_json::json!({"type": kind, "message": "m"}));
+        assert!(matches!(error("authentication_error"), TranslateError::Auth { .. }));
+        assert!(matches!(error("permission_error"), TranslateError::Auth { .. }));
+        assert!(matches!(error("rate_limit_error"), TranslateError::RateLimit { retry_after: None }));
+        assert!(matches!(error("api_error"), TranslateError::Http { status: 500, .. }));
+        assert!(matches!(
+            error("invalid_request_error"),
+            TranslateError::Provider { ref code, ref message } if code == "invalid_request_error" && message == "m"
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+use super::langmap;
+use super::{base_url_or, check_status, Capabilities, TranslateError, TranslateRequest, TranslateResult, Translator};
+use crate::config::BaiduServiceEntry;
+use async_trait::async_trait;
+use reqwest::Client;
+
+// ==================== Baidu Translate ====================
+
+const API_URL: &str = "https://fanyi-api.baidu.com";
+
+pub struct BaiduTranslator {
+    app_id: String,
+    secret_key: String,
+    base_url: String,
+}
+
+impl BaiduTranslator {
+    pub fn new(entry: &BaiduServiceEntry) -> Self {
+        Self {
+            app_id: entry.app_id.clone(),
+            secret_key: entry.secret_key.clone(),
+            base_url: base_url_or(&entry.base_url, API_URL),
+        }
+    }
+}
+
+/// Map Baidu's `error_code` values to error kinds
+fn baidu_error(code: &str, message: &str) -> TranslateError {
+    let message = message.to_string();
+    match code {
+        "52001" => TranslateError::Timeout,
+        "52003" | "54001" | "58000" | "58002" | "90107" => TranslateError::Auth { message },
+        "54003" | "54005" => TranslateError::RateLimit { retry_after: None },
+        "54004" => TranslateError::Quota { message },
+        "58001" => TranslateError::UnsupportedLanguage { lang: message },
+        _ => TranslateError::Provider {
+            code: code.to_string(),
+            message,
+        },
+    }
+}
+
+#[async_trait]
+impl Translator for BaiduTranslator {
+    fn id(&self) -> &'static str {
+        "baidu"
+    }
+
+    fn name(&self) -> String {
+        "Baidu".to_string()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_credentials: true,
+            detects_language: true,
+            streaming: false,
+            dictionary: false,
+            glossary: false,
+            // Baidu recommends under 6000 bytes per request
+            max_chars: 2000,
+            max_batch: 1,
+            prompt_modes: false,
+        }
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        if self.app_id.is_empty() || self.secret_key.is_empty() {
+            return Err(TranslateError::NotConfigured);
+        }
+
+        let (from, to) = langmap::map_pair(self.id(), req)?;
+        let salt: u32 = rand::random();
+        let sign_str = format!("{}{}{}{}", self.app_id, req.text, salt, self.secret_key);
+        let sign = format!("{:x}", md5::compute(sign_str.as_bytes()));
+
+        let params = [
+            ("q", req.text.as_str()),
+            ("from", from.unwrap_or("auto")),
+            ("to", to),
+            ("appid", &self.app_id),
+            ("salt", &salt.to_string()),
+            ("sign", &sign),
+        ];
+
+        let resp = client
+            .post(format!("{}/api/trans/vip/translate", self.base_url))
+            .form(&params)
+            .send()
+            .await?;
+        let json = check_status(resp).await?.json::<serde_json::Value>().await?;
+
+        // The code arrives as a string or a number depending on the endpoint; 52000 is success
+        if let Some(err_code) = json.get("error_code") {
+            let code = err_code
+                .as_str()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| err_code.to_string());
+            if code != "52000" {
+                return Err(baidu_error(&code, json["error_msg"].as_str().unwrap_or("Unknown")));
+            }
+        }
+        let results = json["trans_result"].as_array().ok_or_else(|| TranslateError::Parse {
+            message: "missing trans_result".to_string(),
+        })?;
+        let mut translated = String::new();
+        for r in results {
+            if let Some(dst) = r["dst"].as_str() {
+                if !translated.is_empty() {
+                    translated.push('\n');
+                }
+                translated.push_str(dst);
+            }
+        }
+        let detected = json["from"]
+            .as_str()
+            .map(|code| langmap::from_service_code(self.id(), code))
+            .unwrap_or_else(|| req.source.clone());
+        Ok(TranslateResult::ok(self.name(), translated, detected, &req.target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock::{self, MockServer, Reply};
+
+    #[tokio::test]
+    async fn honours_base_url() {
+        let server = MockServer::start(|_| {
+            Reply::json(serde_json::json!({
+                "from": "en",
+                "to": "zh",
+                "trans_result": [{"src": "Hello", "dst": "你好"}, {"src": "world", "dst": "世界"}]
+            }))
+        })
+        .await;
+        let entry = BaiduServiceEntry {
+            enabled: true,
+            app_id: "app".to_string(),
+            secret_key: "secret".to_string(),
+            base_url: format!("{}/proxy/", server.url),
+        };
+
+        let req = mock::request("Hello\nworld", "auto", "zh-CN");
+        let result = BaiduTranslator::new(&entry)
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好\n世界");
+        assert_eq!(result.source_lang, "en");
+        let sent = &server.requests()[0];
+        assert_eq!(sent.path, "/proxy/api/trans/vip/translate");
+        assert_eq!(sent.form("q").as_deref(), Some("Hello\nworld"));
+        assert_eq!(sent.form("to").as_deref(), Some("zh"));
+        let salt = sent.form("salt").unwrap();
+        let sign = format!("{:x}", md5::compute(format!("appHello\nworld{}secret", salt)));
+        assert_eq!(sent.form("sign"), Some(sign));
+    }
+}
@@ -0,0 +1,304 @@
+use super::langmap;
+use super::{base_url_or, Capabilities, TranslateError, TranslateRequest, TranslateResult, Translator};
+use crate::config::BingServiceEntry;
+use async_trait::async_trait;
+use base64::Engine;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// ==================== Bing / Microsoft Translator ====================
+
+const API_URL: &str = "https://api.cognitive.microsofttranslator.com";
+/// Endpoint the Edge browser translates with, authorized by a short-lived token
+const EDGE_API_URL: &str = "https://api-edge.cognitive.microsofttranslator.com";
+const EDGE_AUTH_URL: &str = "https://edge.microsoft.com/translate/auth";
+
+/// Tokens are dropped this long before they actually expire
+const TOKEN_MARGIN_SECS: u64 = 60;
+
+/// Edge tokens and their expiry (unix seconds) by auth URL, shared by all requests
+static EDGE_TOKEN: OnceLock<Mutex<HashMap<String, (String, u64)>>> = OnceLock::new();
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn token_cache() -> &'static Mutex<HashMap<String, (String, u64)>> {
+    EDGE_TOKEN.get_or_init(Default::default)
+}
+
+/// The `exp` claim of a JWT, without verifying it
+fn jwt_expiry(token: &str) -> Option<u64> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    serde_json::from_slice::<serde_json::Value>(&bytes).ok()?["exp"].as_u64()
+}
+
+/// A cached Edge token, or a fresh one when there is none or it is about to expire
+async fn edge_token(client: &Client, auth_url: &str) -> Result<String, TranslateError> {
+    if let Some((token, expiry)) = token_cache().lock().unwrap().get(auth_url) {
+        if now_secs() + TOKEN_MARGIN_SECS < *expiry {
+            return Ok(token.clone());
+        }
+    }
+    let resp = client
+        .get(auth_url)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        return Err(TranslateError::Auth {
+            message: format!("Edge token request failed: HTTP {}", resp.status().as_u16()),
+        });
+    }
+    let token = resp.text().await?.trim().to_string();
+    // Tokens have lasted 10 minutes; assume that if the claim can't be read
+    let expiry = jwt_expiry(&token).unwrap_or_else(|| now_secs() + 600);
+    token_cache()
+        .lock()
+        .unwrap()
+        .insert(auth_url.to_string(), (token.clone(), expiry));
+    Ok(token)
+}
+
+/// Map a Translator error response (`{"error": {"code": 403001, ...}}`) to an error kind
+async fn bing_error(resp: Response) -> TranslateError {
+    let status = resp.status();
+    let body = resp.text().await.unwrap_or_default();
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let message = json["error"]["message"]
+        .as_str()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| body.chars().take(200).collect());
+    match json["error"]["code"].as_u64() {
+        // Free tier (F0) character quota used up
+        Some(403001) => TranslateError::Quota { message },
+        Some(429000..=429999) => TranslateError::RateLimit { retry_after: None },
+        _ => TranslateError::from_status(status, message, None),
+    }
+}
+
+pub struct BingTranslator {
+    api_key: String,
+    region: String,
+    /// `API_URL` with a key, `EDGE_API_URL` without, unless overridden
+    base_url: String,
+    auth_url: String,
+}
+
+impl BingTranslator {
+    pub fn new(entry: &BingServiceEntry) -> Self {
+        let api_key = entry.api_key.trim().to_string();
+        let default_url = if api_key.is_empty() { EDGE_API_URL } else { API_URL };
+        let base_url = base_url_or(&entry.base_url, default_url);
+        let auth_url = if entry.base_url.trim().is_empty() {
+            EDGE_AUTH_URL.to_string()
+        } else {
+            format!("{}/translate/auth", base_url)
+        };
+        Self {
+            api_key,
+            region: entry.region.trim().to_string(),
+            base_url,
+            auth_url,
+        }
+    }
+
+    /// Subscription key (and region) when configured, otherwise the Edge token
+    async fn authorized(&self, client: &Client, query: &str, body: &serde_json::Value) -> Result<Response, TranslateError> {
+        let send = |base: &str| -> RequestBuilder {
+            client
+                .post(format!("{}/translate?{}", base, query))
+                .json(body)
+        };
+        if !self.api_key.is_empty() {
+            let mut request = send(&self.base_url).header("Ocp-Apim-Subscription-Key", &self.api_key);
+            if !self.region.is_empty() {
+                request = request.header("Ocp-Apim-Subscription-Region", &self.region);
+            }
+            return Ok(request.send().await?);
+        }
+
+        let token = edge_token(client, &self.auth_url).await?;
+        let resp = send(&self.base_url).bearer_auth(&token).send().await?;
+        if resp.status() != StatusCode::UNAUTHORIZED {
+            return Ok(resp);
+        }
+        // Revoked or expired early: fetch a new token and try once more
+        token_cache().lock().unwrap().remove(&self.auth_url);
+        let token = edge_token(client, &self.auth_url).await?;
+        Ok(send(&self.base_url).bearer_auth(&token).send().await?)
+    }
+}
+
+#[async_trait]
+impl Translator for BingTranslator {
+    fn id(&self) -> &'static str {
+        "bing"
+    }
+
+    fn name(&self) -> String {
+        "Bing".to_string()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_credentials: false,
+            detects_language: true,
+            streaming: false,
+            dictionary: false,
+            glossary: false,
+            max_chars: 5000,
+            max_batch: 1,
+            prompt_modes: false,
+        }
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        let (bing_source, bing_target) = langmap::map_pair(self.id(), req)?;
+
+        let query = match bing_source {
+            None => format!("api-version=3.0&to={}", bing_target),
+            Some(from) => format!("api-version=3.0&from={}&to={}", from, bing_target),
+        };
+        let body = serde_json::json!([{"Text": req.text}]);
+
+        let resp = self.authorized(client, &query, &body).await?;
+        if !resp.status().is_success() {
+            return Err(bing_error(resp).await);
+        }
+        let json = resp.json::<serde_json::Value>().await?;
+
+        let translated = json[0]["translations"][0]["text"]
+            .as_str()
+            .ok_or_else(|| TranslateError::Parse {
+                message: "missing translations".to_string(),
+            })?
+            .to_string();
+        let detected = json[0]["detectedLanguage"]["language"]
+            .as_str()
+            .map(|code| langmap::from_service_code(self.id(), code))
+            .unwrap_or_else(|| req.source.clone());
+        Ok(TranslateResult::ok(self.name(), translated, detected, &req.target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock::{self, MockServer, Reply};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    fn entry(server: &MockServer, api_key: &str) -> BingServiceEntry {
+        BingServiceEntry {
+            enabled: true,
+            api_key: api_key.to_string(),
+            region: if api_key.is_empty() { String::new() } else { "eastasia".to_string() },
+            base_url: format!("{}/proxy/", server.url),
+        }
+    }
+
+    fn translation() -> Reply {
+        Reply::json(json!([{
+            "detectedLanguage": {"language": "en", "score": 1.0},
+            "translations": [{"text": "你好", "to": "zh-Hans"}]
+        }]))
+    }
+
+    #[tokio::test]
+    async fn subscription_key_honours_base_url() {
+        let server = MockServer::start(|_| translation()).await;
+
+        let req = mock::request("Hello", "auto", "zh-CN");
+        let result = BingTranslator::new(&entry(&server, "azure-key"))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好");
+        assert_eq!(result.source_lang, "en");
+        let sent = &server.requests()[0];
+        assert_eq!(sent.path, "/proxy/translate?api-version=3.0&to=zh-Hans");
+        assert_eq!(sent.header("ocp-apim-subscription-key"), Some("azure-key"));
+        assert_eq!(sent.header("ocp-apim-subscription-region"), Some("eastasia"));
+        assert_eq!(sent.json(), json!([{"Text": "Hello"}]));
+    }
+
+    #[tokio::test]
+    async fn edge_token_comes_from_base_url() {
+        let server = MockServer::start(|r| {
+            if r.path.ends_with("/translate/auth") {
+                Reply::text("edge-token")
+            } else {
+                translation()
+            }
+        })
+        .await;
+
+        let req = mock::request("Hello", "en", "zh-CN");
+        let result = BingTranslator::new(&entry(&server, ""))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好");
+        let sent = server.requests();
+        assert_eq!(sent[0].method, "GET");
+        assert_eq!(sent[0].path, "/proxy/translate/auth");
+        assert_eq!(sent[1].path, "/proxy/translate?api-version=3.0&from=en&to=zh-Hans");
+        assert_eq!(sent[1].header("authorization"), Some("Bearer edge-token"));
+    }
+
+    /// An Edge endpoint whose auth hands out `token`; translations are
+    /// refused with 401 while `reject` is set
+    async fn edge_server(token: &'static str, reject: Arc<AtomicBool>) -> MockServer {
+        MockServer::start(move |r| {
+            if r.path.ends_with("/translate/auth") {
+                Reply::text(token)
+            } else if reject.swap(false, Ordering::SeqCst) {
+                Reply::status(401, json!({"error": {"code": 401000, "message": "expired"}}))
+            } else {
+                translation()
+            }
+        })
+        .await
+    }
+
+    fn token_fetches(server: &MockServer) -> usize {
+        server.requests().iter().filter(|r| r.method == "GET").count()
+    }
+
+    #[tokio::test]
+    async fn edge_tokens_are_kept_per_auth_url() {
+        let reject = Arc::new(AtomicBool::new(false));
+        let first = edge_server("token-a", Arc::new(AtomicBool::new(false))).await;
+        let second = edge_server("token-b", reject.clone()).await;
+        let first_bing = BingTranslator::new(&entry(&first, ""));
+        let second_bing = BingTranslator::new(&entry(&second, ""));
+        let client = Client::new();
+        let req = mock::request("Hello", "en", "zh-CN");
+
+        first_bing.translate(&client, &req).await.unwrap();
+        second_bing.translate(&client, &req).await.unwrap();
+        first_bing.translate(&client, &req).await.unwrap();
+        assert_eq!(token_fetches(&first), 1);
+        assert_eq!(first.requests().last().unwrap().header("authorization"), Some("Bearer token-a"));
+        assert_eq!(second.requests().last().unwrap().header("authorization"), Some("Bearer token-b"));
+
+        // A 401 renews only the token of the endpoint that refused it
+        reject.store(true, Ordering::SeqCst);
+        second_bing.translate(&client, &req).await.unwrap();
+        assert_eq!(token_fetches(&second), 2);
+        first_bing.translate(&client, &req).await.unwrap();
+        assert_eq!(token_fetches(&first), 1);
+    }
+}
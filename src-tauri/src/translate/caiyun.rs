@@ -0,0 +1,111 @@
+use super::langmap;
+use super::{base_url_or, check_status, Capabilities, TranslateError, TranslateRequest, TranslateResult, Translator};
+use crate::config::ServiceEntry;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::Value;
+
+// ==================== Caiyun Xiaoyi ====================
+
+const API_URL: &str = "https://api.interpreter.caiyunai.com";
+
+pub struct CaiyunTranslator {
+    token: String,
+    base_url: String,
+}
+
+impl CaiyunTranslator {
+    pub fn new(entry: &ServiceEntry) -> Self {
+        Self {
+            token: entry.api_key.clone(),
+            base_url: base_url_or(&entry.base_url, API_URL),
+        }
+    }
+}
+
+#[async_trait]
+impl Translator for CaiyunTranslator {
+    fn id(&self) -> &'static str {
+        "caiyun"
+    }
+
+    fn name(&self) -> String {
+        "Caiyun".to_string()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_credentials: true,
+            // The response doesn't say what "auto" resolved to
+            detects_language: false,
+            streaming: false,
+            dictionary: false,
+            glossary: false,
+            max_chars: 5000,
+            max_batch: 1,
+            prompt_modes: false,
+        }
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        if self.token.is_empty() {
+            return Err(TranslateError::NotConfigured);
+        }
+
+        let (source, target) = langmap::map_pair(self.id(), req)?;
+        let body = serde_json::json!({
+            "source": [req.text],
+            "trans_type": format!("{}2{}", source.unwrap_or("auto"), target),
+            "request_id": "easydict",
+            "detect": true
+        });
+
+        let resp = client
+            .post(format!("{}/v1/translator", self.base_url))
+            .header("x-authorization", format!("token {}", self.token))
+            .json(&body)
+            .send()
+            .await?;
+        let json = check_status(resp).await?.json::<Value>().await?;
+
+        let translated = json["target"]
+            .as_array()
+            .ok_or_else(|| TranslateError::Parse {
+                message: "missing target".to_string(),
+            })?
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join("\n");
+        Ok(TranslateResult::ok(self.name(), translated, req.source.clone(), &req.target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock::{self, MockServer, Reply};
+
+    #[tokio::test]
+    async fn honours_base_url() {
+        let server = MockServer::start(|_| Reply::json(serde_json::json!({"target": ["你好", "世界"], "rc": 0}))).await;
+        let entry = ServiceEntry {
+            enabled: true,
+            api_key: "caiyun-token".to_string(),
+            base_url: format!("{}/proxy/", server.url),
+        };
+
+        let req = mock::request("Hello world", "en", "zh-CN");
+        let result = CaiyunTranslator::new(&entry)
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好\n世界");
+        let sent = &server.requests()[0];
+        assert_eq!(sent.path, "/proxy/v1/translator");
+        assert_eq!(sent.header("x-authorization"), Some("token caiyun-token"));
+        assert_eq!(sent.json()["trans_type"], "en2zh");
+        assert_eq!(sent.json()["source"], serde_json::json!(["Hello world"]));
+    }
+}
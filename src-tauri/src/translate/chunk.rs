@@ -0,0 +1,399 @@
+use super::{Capabilities, StreamDelta, TranslateError, TranslateRequest, TranslateResult, Translator};
+use async_trait::async_trait;
+use futures_util::stream::{self, StreamExt, TryStreamExt};
+use reqwest::Client;
+
+// ==================== Long Text Chunking ====================
+
+/// Chunks of one text in flight at once per service
+const MAX_PARALLEL: usize = 4;
+
+/// A chunk of the input and the whitespace that followed it in the original,
+/// put back verbatim between the translated chunks
+#[derive(Debug, Clone, PartialEq)]
+struct Piece {
+    text: String,
+    sep: String,
+}
+
+const CLOSERS: &[char] = &['"', '\'', ')', ']', '”', '’', '」', '』', '）', '】', '》'];
+
+/// Paragraphs with the line breaks (and indentation) after them
+pub(super) fn paragraphs(text: &str) -> Vec<(&str, &str)> {
+    let mut out = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let Some(i) = rest.find('\n') else {
+            out.push((rest, ""));
+            break;
+        };
+        let content_end = rest[..i].trim_end().len();
+        let sep_end = rest.len() - rest[i..].trim_start().len();
+        out.push((&rest[..content_end], &rest[content_end..sep_end]));
+        rest = &rest[sep_end..];
+    }
+    out
+}
+
+/// Sentences with the whitespace after them. CJK terminators end a sentence
+/// outright, Latin ones only before whitespace (so "3.14" stays whole).
+fn sentences(text: &str) -> Vec<(&str, &str)> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let cjk = matches!(c, '。' | '！' | '？' | '；' | '…');
+        let latin = matches!(c, '.' | '!' | '?' | ';');
+        if !cjk && !latin {
+            continue;
+        }
+        let mut end = i + c.len_utf8();
+        while let Some(&(j, next)) = chars.peek() {
+            if !CLOSERS.contains(&next) {
+                break;
+            }
+            end = j + next.len_utf8();
+            chars.next();
+        }
+        let followed_by_space = text[end..].starts_with(char::is_whitespace);
+        if latin && !followed_by_space {
+            continue;
+        }
+        let sep_end = text.len() - text[end..].trim_start().len();
+        out.push((&text[start..end], &text[end..sep_end]));
+        start = sep_end;
+        while chars.peek().is_some_and(|&(j, _)| j < sep_end) {
+            chars.next();
+        }
+    }
+    if start < text.len() {
+        out.push((&text[start..], ""));
+    }
+    out
+}
+
+/// Cut an over-long sentence at the last whitespace before the limit, or at the limit
+fn hard_split(text: &str, max_chars: usize) -> Vec<(&str, &str)> {
+    let mut out = Vec::new();
+    let mut rest = text;
+    while let Some((limit, _)) = rest.char_indices().nth(max_chars) {
+        let cut = rest[..limit]
+            .rfind(char::is_whitespace)
+            .filter(|&i| i > 0)
+            .unwrap_or(limit);
+        let head = rest[..cut].trim_end();
+        let next = rest[cut..].trim_start();
+        out.push((head, &rest[head.len()..rest.len() - next.len()]));
+        rest = next;
+    }
+    out.push((rest, ""));
+    out
+}
+
+/// Split `text` into pieces of at most `max_chars` characters. Paragraphs are
+/// kept whole when they fit, otherwise split into sentences; consecutive units
+/// are packed together so short paragraphs share a request.
+fn split(text: &str, max_chars: usize) -> Vec<Piece> {
+    let mut units: Vec<(&str, &str)> = Vec::new();
+    for (para, para_sep) in paragraphs(text) {
+        if para.chars().count() <= max_chars {
+            units.push((para, para_sep));
+            continue;
+        }
+        for (sentence, sentence_sep) in sentences(para) {
+            units.extend(hard_split(sentence, max_chars));
+            if let Some(last) = units.last_mut() {
+                last.1 = sentence_sep;
+            }
+        }
+        if let Some(last) = units.last_mut() {
+            last.1 = para_sep;
+        }
+    }
+
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut pending_sep = "";
+    for (unit, sep) in units {
+        let len = current.chars().count() + pending_sep.chars().count() + unit.chars().count();
+        if !current.is_empty() && len > max_chars {
+            pieces.push(Piece {
+                text: std::mem::take(&mut current),
+                sep: pending_sep.to_string(),
+            });
+        } else {
+            current.push_str(pending_sep);
+        }
+        current.push_str(unit);
+        pending_sep = sep;
+    }
+    if !current.is_empty() || !pending_sep.is_empty() {
+        pieces.push(Piece {
+            text: current,
+            sep: pending_sep.to_string(),
+        });
+    }
+    pieces
+}
+
+/// Wraps a translator so input over its `max_chars` is translated chunk by
+/// chunk and reassembled with the original breaks
+pub struct Chunked {
+    inner: Box<dyn Translator>,
+}
+
+impl Chunked {
+    pub fn new(inner: Box<dyn Translator>) -> Self {
+        Self { inner }
+    }
+
+    async fn translate_piece(&self, client: &Client, req: TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        if req.text.trim().is_empty() {
+            return Ok(TranslateResult::ok(self.name(), req.text, req.source, &req.target));
+        }
+        self.inner.translate(client, &req).await
+    }
+
+    /// Non-blank pieces sent `max_batch` at a time through `translate_batch`
+    async fn translate_batched(
+        &self,
+        client: &Client,
+        req: &TranslateRequest,
+        pieces: &[Piece],
+        max_batch: usize,
+    ) -> Result<Vec<TranslateResult>, TranslateError> {
+        let texts: Vec<String> = pieces
+            .iter()
+            .filter(|p| !p.text.trim().is_empty())
+            .map(|p| p.text.clone())
+            .collect();
+        let batches: Vec<Vec<String>> = texts.chunks(max_batch).map(<[String]>::to_vec).collect();
+        let batches: Vec<Vec<TranslateResult>> = stream::iter(batches)
+            .map(|batch| async move { self.inner.translate_batch(client, req, &batch).await })
+            .buffered(MAX_PARALLEL)
+            .try_collect()
+            .await?;
+        let mut translated = batches.into_iter().flatten();
+        pieces
+            .iter()
+            .map(|p| {
+                if p.text.trim().is_empty() {
+                    return Ok(TranslateResult::ok(self.name(), p.text.clone(), req.source.clone(), &req.target));
+                }
+                translated.next().ok_or_else(|| TranslateError::Parse {
+                    message: "batch returned fewer results than texts".to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Translator for Chunked {
+    fn id(&self) -> &'static str {
+        self.inner.id()
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn instance_key(&self) -> String {
+        self.inner.instance_key()
+    }
+
+    fn supported_languages(&self) -> Vec<&'static str> {
+        self.inner.supported_languages()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities()
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        let max_chars = self.capabilities().max_chars;
+        if max_chars == 0 || req.text.chars().count() <= max_chars {
+            return self.inner.translate(client, req).await;
+        }
+
+        let pieces = split(&req.text, max_chars);
+        let caps = self.capabilities();
+        let requests: Vec<TranslateRequest> = pieces
+            .iter()
+            .map(|p| TranslateRequest {
+                text: p.text.clone(),
+                ..req.clone()
+            })
+            .collect();
+        let results: Vec<TranslateResult> = match &req.on_delta {
+            // Batching services don't stream, so there is no order to keep
+            _ if caps.max_batch > 1 => self.translate_batched(client, req, &pieces, caps.max_batch).await?,
+            // Streamed output has to reach the UI in order, so chunks go one at a time
+            Some(sink) if caps.streaming => {
+                let mut results = Vec::with_capacity(pieces.len());
+                for (i, chunk_req) in requests.into_iter().enumerate() {
+                    if i > 0 {
+                        sink(StreamDelta {
+                            service: self.name(),
+                            delta: pieces[i - 1].sep.clone(),
+                        });
+                    }
+                    results.push(self.translate_piece(client, chunk_req).await?);
+                }
+                results
+            }
+            _ => {
+                stream::iter(requests)
+                    .map(|chunk_req| self.translate_piece(client, chunk_req))
+                    .buffered(MAX_PARALLEL)
+                    .try_collect()
+                    .await?
+            }
+        };
+
+        let mut translated = String::new();
+        for (piece, result) in pieces.iter().zip(&results) {
+            translated.push_str(&result.translated);
+            translated.push_str(&piece.sep);
+        }
+        let source_lang = results
+            .first()
+            .map(|r| r.source_lang.clone())
+            .unwrap_or_else(|| req.source.clone());
+        Ok(TranslateResult::ok(self.name(), translated, source_lang, &req.target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn rejoin(pieces: &[Piece]) -> String {
+        pieces.iter().map(|p| format!("{}{}", p.text, p.sep)).collect()
+    }
+
+    #[test]
+    fn split_round_trips_separators() {
+        let text = "First paragraph. It has two sentences!\n\n  Indented second one?  \r\n\nThird 第三段。还有一句！\n";
+        for max_chars in [10, 20, 40, 1000] {
+            let pieces = split(text, max_chars);
+            assert_eq!(rejoin(&pieces), text, "max_chars {}", max_chars);
+            assert!(pieces.iter().all(|p| p.text.chars().count() <= max_chars));
+        }
+    }
+
+    #[test]
+    fn short_paragraphs_share_a_piece() {
+        let pieces = split("a\nb\n\nc", 100);
+        assert_eq!(pieces, [Piece { text: "a\nb\n\nc".to_string(), sep: String::new() }]);
+    }
+
+    #[test]
+    fn sentences_keep_decimals_whole() {
+        assert_eq!(
+            sentences("Pi is 3.14 today. Really?  \"Yes.\" Done"),
+            [("Pi is 3.14 today.", " "), ("Really?", "  "), ("\"Yes.\"", " "), ("Done", "")]
+        );
+    }
+
+    #[test]
+    fn sentences_end_at_cjk_terminators() {
+        assert_eq!(
+            sentences("你好。世界！「再见？」好"),
+            [("你好。", ""), ("世界！", ""), ("「再见？」", ""), ("好", "")]
+        );
+    }
+
+    #[test]
+    fn hard_split_prefers_whitespace() {
+        assert_eq!(hard_split("aaa bbb ccc", 8), [("aaa bbb", " "), ("ccc", "")]);
+    }
+
+    #[test]
+    fn hard_split_cuts_long_words() {
+        let word = "x".repeat(25);
+        let parts = hard_split(&word, 10);
+        assert_eq!(parts.iter().map(|(t, _)| t.len()).collect::<Vec<_>>(), [10, 10, 5]);
+        assert!(parts.iter().all(|(_, sep)| sep.is_empty()));
+
+        let text = format!("ab {} cd", word);
+        let pieces = split(&text, 10);
+        assert_eq!(rejoin(&pieces), text);
+        assert!(pieces.iter().all(|p| p.text.chars().count() <= 10));
+    }
+
+    /// Echoes its input after a pause, tracking how many calls overlap
+    struct Echo {
+        streaming: bool,
+        active: AtomicUsize,
+        peak: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Translator for Echo {
+        fn id(&self) -> &'static str {
+            "google"
+        }
+
+        fn name(&self) -> String {
+            "Echo".to_string()
+        }
+
+        fn capabilities(&self) -> Capabilities {
+            Capabilities {
+                requires_credentials: false,
+                detects_language: false,
+                streaming: self.streaming,
+                dictionary: false,
+                glossary: false,
+                max_chars: 10,
+                max_batch: 1,
+                prompt_modes: false,
+            }
+        }
+
+        async fn translate(&self, _: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+            let now = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+            Ok(TranslateResult::ok(self.name(), req.text.to_uppercase(), req.source.clone(), &req.target))
+        }
+    }
+
+    async fn run(streaming: bool) -> (String, usize, Vec<String>) {
+        let peak = Arc::new(AtomicUsize::new(0));
+        let echo = Echo {
+            streaming,
+            active: AtomicUsize::new(0),
+            peak: peak.clone(),
+        };
+        let (req, deltas) = mock::streaming_request("one two. three. four. five six.", "en", "de");
+        let result = Chunked::new(Box::new(echo))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+        let deltas = deltas.lock().unwrap().clone();
+        (result.translated, peak.load(Ordering::SeqCst), deltas)
+    }
+
+    #[tokio::test]
+    async fn non_streaming_chunks_run_concurrently() {
+        let (translated, peak, deltas) = run(false).await;
+        assert_eq!(translated, "ONE TWO. THREE. FOUR. FIVE SIX.");
+        assert!(peak > 1);
+        assert!(deltas.is_empty());
+    }
+
+    #[tokio::test]
+    async fn streaming_chunks_run_in_order() {
+        let (translated, peak, deltas) = run(true).await;
+        assert_eq!(translated, "ONE TWO. THREE. FOUR. FIVE SIX.");
+        assert_eq!(peak, 1);
+        assert_eq!(deltas, [" ", " ", " "]);
+    }
+}
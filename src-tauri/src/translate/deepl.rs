@@ -0,0 +1,563 @@
+use super::langmap;
+use super::sign::sha256_hex;
+use super::{base_url_or, check_status, Capabilities, TranslateError, TranslateRequest, TranslateResult, Translator};
+use crate::config::DeepLServiceEntry;
+use crate::glossary::{self, GlossaryEntry};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock};
+
+// ==================== DeepL Translate ====================
+
+pub struct DeepLTranslator {
+    api_key: String,
+    /// api-free.deepl.com for ":fx" keys, else api.deepl.com, unless overridden
+    host: String,
+    formality: String,
+    english: String,
+    portuguese: String,
+}
+
+/// Name of every glossary this app creates on the account
+const GLOSSARY_NAME: &str = "EasyDict";
+
+/// A language pair's glossary as (TSV contents, glossary id). Locked while the
+/// pair is synced, so concurrent batches of one request share a single glossary.
+type Slot = Arc<tokio::sync::Mutex<Option<(String, String)>>>;
+
+/// Glossaries this app keeps on DeepL accounts. One glossary per language pair
+/// is kept; it is replaced when the entries change.
+#[derive(Default)]
+struct Glossaries {
+    /// Accounts whose glossaries from earlier runs were deleted
+    cleaned: HashSet<String>,
+    /// account|source|target -> that pair's glossary
+    current: HashMap<String, Slot>,
+    /// Ids of the glossaries this app created, by account hash; persisted so
+    /// the next run deletes only its own
+    created: HashMap<String, Vec<String>>,
+}
+
+static GLOSSARIES: OnceLock<Mutex<Glossaries>> = OnceLock::new();
+
+#[cfg(not(test))]
+fn created_path() -> PathBuf {
+    crate::config::data_dir().join("deepl_glossaries.json")
+}
+
+#[cfg(test)]
+fn created_path() -> PathBuf {
+    std::env::temp_dir().join(format!("easydict-deepl-glossaries-{}.json", std::process::id()))
+}
+
+/// Only held to read or update the maps, never across a request
+fn glossaries() -> MutexGuard<'static, Glossaries> {
+    GLOSSARIES
+        .get_or_init(|| {
+            let data = fs::read_to_string(created_path()).unwrap_or_default();
+            Mutex::new(Glossaries {
+                created: serde_json::from_str(&data).unwrap_or_default(),
+                ..Default::default()
+            })
+        })
+        .lock()
+        .unwrap()
+}
+
+impl Glossaries {
+    fn save(&self) {
+        if let Ok(data) = serde_json::to_string_pretty(&self.created) {
+            fs::write(created_path(), data).ok();
+        }
+    }
+
+    fn remember(&mut self, account: &str, id: &str) {
+        self.created.entry(account.to_string()).or_default().push(id.to_string());
+        self.save();
+    }
+
+    fn forget(&mut self, account: &str, id: &str) {
+        if let Some(ids) = self.created.get_mut(account) {
+            ids.retain(|i| i != id);
+            if ids.is_empty() {
+                self.created.remove(account);
+            }
+            self.save();
+        }
+    }
+}
+
+/// Source languages and glossaries take the base of the target code (EN-US -> EN)
+fn base_code(code: &str) -> &str {
+    code.split('-').next().unwrap_or(code)
+}
+
+/// Markup DeepL should leave intact: "xml" for documents with an XML
+/// declaration, "html" for text with tags, `None` for plain text
+fn tag_handling(text: &str) -> Option<&'static str> {
+    let text = text.trim_start();
+    if text.starts_with("<?xml") {
+        return Some("xml");
+    }
+    let opens_tag = text
+        .match_indices('<')
+        .any(|(i, _)| text[i + 1..].starts_with(|c: char| c.is_ascii_alphabetic()));
+    (opens_tag && (text.contains("</") || text.contains("/>"))).then_some("html")
+}
+
+impl DeepLTranslator {
+    pub fn new(entry: &DeepLServiceEntry) -> Self {
+        let host = if entry.api_key.ends_with(":fx") {
+            "https://api-free.deepl.com"
+        } else {
+            "https://api.deepl.com"
+        };
+        Self {
+            api_key: entry.api_key.clone(),
+            host: base_url_or(&entry.base_url, host),
+            formality: entry.formality.clone(),
+            english: entry.english_variant.clone(),
+            portuguese: entry.portuguese_variant.clone(),
+        }
+    }
+
+    /// The configured variant for targets that have two (EN-US/EN-GB, PT-BR/PT-PT)
+    fn target_variant<'a>(&'a self, target: &'a str) -> &'a str {
+        match target {
+            "EN-US" | "EN-GB" if !self.english.is_empty() => &self.english,
+            "PT-BR" | "PT-PT" if !self.portuguese.is_empty() => &self.portuguese,
+            _ => target,
+        }
+    }
+
+    /// Hash of host and key, so the persisted ids don't reveal the key
+    fn account(&self) -> String {
+        sha256_hex(format!("{}|{}", self.host, self.api_key).as_bytes())
+    }
+
+    fn slot(&self, source: &str, target: &str) -> Slot {
+        let key = format!("{}|{}|{}", self.account(), source, target);
+        glossaries().current.entry(key).or_default().clone()
+    }
+
+    async fn delete_glossary(&self, client: &Client, id: &str) {
+        client
+            .delete(format!("{}/v2/glossaries/{}", self.host, id))
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .send()
+            .await
+            .ok();
+        glossaries().forget(&self.account(), id);
+    }
+
+    /// Delete the glossaries earlier runs of this app created on the account,
+    /// once per run. Glossaries made by anything else are left alone.
+    async fn delete_leftover_glossaries(&self, client: &Client) {
+        let account = self.account();
+        let leftovers = {
+            let mut glossaries = glossaries();
+            if !glossaries.cleaned.insert(account.clone()) {
+                return;
+            }
+            glossaries.created.get(&account).cloned().unwrap_or_default()
+        };
+        for id in leftovers {
+            self.delete_glossary(client, &id).await;
+        }
+    }
+
+    /// Id of a DeepL glossary holding `entries`, created on first use.
+    /// `None` when DeepL rejects it (e.g. unsupported pair).
+    async fn glossary_id(
+        &self,
+        client: &Client,
+        source: &str,
+        target: &str,
+        entries: &[GlossaryEntry],
+    ) -> Option<String> {
+        let tsv: String = entries
+            .iter()
+            .map(|e| format!("{}\t{}\n", e.term, e.output()))
+            .collect();
+        // Only this language pair waits while its glossary is replaced
+        let slot = self.slot(source, target);
+        let mut current = slot.lock().await;
+
+        self.delete_leftover_glossaries(client).await;
+        if let Some((contents, id)) = current.as_ref() {
+            if *contents == tsv {
+                return Some(id.clone());
+            }
+        }
+        if let Some((_, old_id)) = current.take() {
+            self.delete_glossary(client, &old_id).await;
+        }
+
+        let params = [
+            ("name", GLOSSARY_NAME),
+            ("source_lang", source),
+            ("target_lang", target),
+            ("entries", &tsv),
+            ("entries_format", "tsv"),
+        ];
+        let resp = client
+            .post(format!("{}/v2/glossaries", self.host))
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .form(&params)
+            .send()
+            .await
+            .ok()?;
+        if !resp.status().is_success() {
+            eprintln!("[DeepL] Glossary rejected: {}", resp.status());
+            return None;
+        }
+        let json: serde_json::Value = resp.json().await.ok()?;
+        let id = json["glossary_id"].as_str()?.to_string();
+        glossaries().remember(&self.account(), &id);
+        *current = Some((tsv, id.clone()));
+        Some(id)
+    }
+
+    /// Stop using a glossary DeepL no longer accepts, e.g. one deleted elsewhere
+    async fn drop_glossary(&self, source: &str, target: &str, id: &str) {
+        let slot = self.slot(source, target);
+        let mut current = slot.lock().await;
+        if current.as_ref().is_some_and(|(_, current_id)| current_id == id) {
+            *current = None;
+        }
+        glossaries().forget(&self.account(), id);
+    }
+
+    /// One `/v2/translate` call for all of `texts`, which share `req`'s languages.
+    /// Repeated without the glossary once if DeepL no longer has it.
+    async fn translate_texts(
+        &self,
+        client: &Client,
+        req: &TranslateRequest,
+        texts: &[String],
+    ) -> Result<Vec<TranslateResult>, TranslateError> {
+        if self.api_key.is_empty() {
+            return Err(TranslateError::NotConfigured);
+        }
+
+        let (source, deepl_target) = langmap::map_pair(self.id(), req)?;
+        let deepl_target = self.target_variant(deepl_target);
+
+        // Glossaries need an explicit source language, so fall back to local detection
+        let source = match source {
+            None if !req.glossary.is_empty() => langmap::service_code(self.id(), &req.detected),
+            other => other,
+        }
+        .map(base_code);
+
+        let pair = source
+            .filter(|_| !req.glossary.is_empty())
+            .map(|source| (source, base_code(deepl_target)));
+        let glossary_id = match pair {
+            Some((source, target)) => self.glossary_id(client, source, target, &req.glossary).await,
+            None => None,
+        };
+
+        let sent = self
+            .send_texts(client, req, texts, source, deepl_target, glossary_id.as_deref())
+            .await;
+        match (sent, pair, glossary_id) {
+            // The glossary was deleted elsewhere: forget it and go without
+            (Err(TranslateError::Http { status, message }), Some((source, target)), Some(id))
+                if status == 404 || (status == 400 && message.to_lowercase().contains("glossary")) =>
+            {
+                eprintln!("[DeepL] Glossary {} rejected, translating without it: {}", id, message);
+                self.drop_glossary(source, target, &id).await;
+                self.send_texts(client, req, texts, Some(source), deepl_target, None)
+                    .await
+            }
+            (sent, _, _) => sent,
+        }
+    }
+
+    /// One `/v2/translate` request, using `glossary_id` or else protecting the
+    /// glossary terms with placeholders
+    async fn send_texts(
+        &self,
+        client: &Client,
+        req: &TranslateRequest,
+        texts: &[String],
+        source: Option<&str>,
+        deepl_target: &str,
+        glossary_id: Option<&str>,
+    ) -> Result<Vec<TranslateResult>, TranslateError> {
+        // No native glossary for this pair: protect the terms with placeholders instead
+        let protect_terms = glossary_id.is_none() && !req.glossary.is_empty();
+        let (texts, outputs): (Vec<String>, Vec<Vec<String>>) = texts
+            .iter()
+            .map(|text| {
+                if protect_terms {
+                    glossary::protect(text, &req.glossary)
+                } else {
+                    (text.clone(), Vec::new())
+                }
+            })
+            .unzip();
+
+        let mut body = serde_json::json!({
+            "text": texts,
+            "target_lang": deepl_target
+        });
+        if let Some(source) = source {
+            body["source_lang"] = serde_json::json!(source);
+        }
+        if let Some(id) = glossary_id {
+            body["glossary_id"] = serde_json::json!(id);
+        }
+        if !self.formality.is_empty() && self.formality != "default" {
+            body["formality"] = serde_json::json!(self.formality);
+        }
+        // Context steers the translation but is neither translated nor billed
+        if !req.context.is_empty() {
+            body["context"] = serde_json::json!(req.context);
+        }
+        if let Some(kind) = texts.iter().find_map(|text| tag_handling(text)) {
+            body["tag_handling"] = serde_json::json!(kind);
+        }
+
+        let resp = client
+            .post(format!("{}/v2/translate", self.host))
+            .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+            .json(&body)
+            .send()
+            .await?;
+        let json = check_status(resp).await?.json::<serde_json::Value>().await?;
+
+        let translations = json["translations"]
+            .as_array()
+            .filter(|t| t.len() == texts.len())
+            .ok_or_else(|| TranslateError::Parse {
+                message: "missing translations".to_string(),
+            })?;
+        translations
+            .iter()
+            .zip(&outputs)
+            .map(|(t, outputs)| {
+                let translated = t["text"].as_str().ok_or_else(|| TranslateError::Parse {
+                    message: "missing translation text".to_string(),
+                })?;
+                // DeepL reports base codes ("ZH"), so an explicit source is the more precise answer
+                let detected = match t["detected_source_language"].as_str() {
+                    Some(code) if req.source == "auto" => langmap::from_service_code(self.id(), code),
+                    _ => req.source.clone(),
+                };
+                Ok(TranslateResult::ok(
+                    self.name(),
+                    glossary::restore(translated, outputs),
+                    detected,
+                    &req.target,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Characters used and allowed in the current billing period, from `/v2/usage`
+pub async fn load_usage(client: &Client, entry: &DeepLServiceEntry) -> Result<(u64, u64), TranslateError> {
+    let translator = DeepLTranslator::new(entry);
+    let resp = client
+        .get(format!("{}/v2/usage", translator.host))
+        .header("Authorization", format!("DeepL-Auth-Key {}", translator.api_key))
+        .send()
+        .await?;
+    let json = check_status(resp).await?.json::<serde_json::Value>().await?;
+    match (json["character_count"].as_u64(), json["character_limit"].as_u64()) {
+        (Some(count), Some(limit)) => Ok((count, limit)),
+        _ => Err(TranslateError::Parse {
+            message: "missing character_count".to_string(),
+        }),
+    }
+}
+
+#[async_trait]
+impl Translator for DeepLTranslator {
+    fn id(&self) -> &'static str {
+        "deepl"
+    }
+
+    fn name(&self) -> String {
+        "DeepL".to_string()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_credentials: true,
+            detects_language: true,
+            streaming: false,
+            dictionary: false,
+            glossary: true,
+            max_chars: 5000,
+            // 6 x 5000 CJK chars as JSON stay under DeepL's 128 KiB request limit
+            max_batch: 6,
+            prompt_modes: false,
+        }
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        self.translate_texts(client, req, std::slice::from_ref(&req.text))
+            .await?
+            .pop()
+            .ok_or_else(|| TranslateError::Parse {
+                message: "missing translations".to_string(),
+            })
+    }
+
+    async fn translate_batch(
+        &self,
+        client: &Client,
+        req: &TranslateRequest,
+        texts: &[String],
+    ) -> Result<Vec<TranslateResult>, TranslateError> {
+        self.translate_texts(client, req, texts).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock::{self, MockServer, Reply};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn entry(server: &MockServer) -> DeepLServiceEntry {
+        DeepLServiceEntry {
+            enabled: true,
+            api_key: "key:fx".to_string(),
+            formality: "default".to_string(),
+            english_variant: "EN-GB".to_string(),
+            portuguese_variant: "PT-BR".to_string(),
+            base_url: server.url.clone(),
+        }
+    }
+
+    fn deepl_mock(r: &mock::Recorded) -> Reply {
+        match (r.method.as_str(), r.path.as_str()) {
+            ("POST", "/v2/glossaries") => Reply::json(json!({"glossary_id": "new-1"})),
+            ("DELETE", _) => Reply::text(""),
+            _ => {
+                let count = r.json()["text"].as_array().map_or(0, |t| t.len());
+                let translations: Vec<_> = (0..count)
+                    .map(|i| json!({"detected_source_language": "EN", "text": format!("t{}", i)}))
+                    .collect();
+                Reply::json(json!({ "translations": translations }))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn translates_batch_with_options() {
+        let server = MockServer::start(deepl_mock).await;
+        let mut req = mock::request("ignored", "auto", "en");
+        req.context = "Around the text".to_string();
+        let texts = vec!["<b>one</b>".to_string(), "two".to_string()];
+
+        let results = DeepLTranslator::new(&entry(&server))
+            .translate_batch(&Client::new(), &req, &texts)
+            .await
+            .unwrap();
+
+        assert_eq!(results.iter().map(|r| r.translated.as_str()).collect::<Vec<_>>(), ["t0", "t1"]);
+        let sent = &server.requests()[0];
+        assert_eq!(sent.header("authorization"), Some("DeepL-Auth-Key key:fx"));
+        let body = sent.json();
+        assert_eq!(body["target_lang"], "EN-GB");
+        assert_eq!(body["context"], "Around the text");
+        assert_eq!(body["tag_handling"], "html");
+        assert!(body.get("formality").is_none());
+    }
+
+    fn glossary_request() -> TranslateRequest {
+        let mut req = mock::request("ignored", "en", "de");
+        req.glossary = vec![GlossaryEntry {
+            id: 1,
+            source_lang: "en".to_string(),
+            target_lang: "de".to_string(),
+            term: "Tauri".to_string(),
+            translation: String::new(),
+        }];
+        req
+    }
+
+    #[tokio::test]
+    async fn concurrent_batches_share_one_glossary() {
+        let server = MockServer::start(deepl_mock).await;
+        let req = glossary_request();
+        let translator = DeepLTranslator::new(&entry(&server));
+        let client = Client::new();
+        // Left by an earlier run
+        glossaries().created.insert(translator.account(), vec!["old-1".to_string()]);
+        let first = vec!["Tauri one".to_string()];
+        let second = vec!["Tauri two".to_string()];
+
+        let (a, b) = tokio::join!(
+            translator.translate_batch(&client, &req, &first),
+            translator.translate_batch(&client, &req, &second),
+        );
+        a.unwrap();
+        b.unwrap();
+        // A second sync for the same account only reuses the glossary
+        translator.translate_batch(&client, &req, &first).await.unwrap();
+
+        let requests = server.requests();
+        let count = |method: &str, path: &str| {
+            requests.iter().filter(|r| r.method == method && r.path.starts_with(path)).count()
+        };
+        assert_eq!(count("GET", "/v2/glossaries"), 0);
+        assert_eq!(count("POST", "/v2/glossaries"), 1);
+        let deleted: Vec<_> = requests.iter().filter(|r| r.method == "DELETE").map(|r| r.path.as_str()).collect();
+        assert_eq!(deleted, ["/v2/glossaries/old-1"]);
+        for r in requests.iter().filter(|r| r.path == "/v2/translate") {
+            assert_eq!(r.json()["glossary_id"], "new-1");
+        }
+        assert_eq!(glossaries().created[&translator.account()], ["new-1"]);
+    }
+
+    #[tokio::test]
+    async fn glossary_deleted_elsewhere_is_dropped() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let counter = created.clone();
+        let server = MockServer::start(move |r| match (r.method.as_str(), r.path.as_str()) {
+            ("POST", "/v2/glossaries") => {
+                let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                Reply::json(json!({ "glossary_id": format!("new-{}", n) }))
+            }
+            _ if r.json()["glossary_id"] == "new-1" => Reply::status(404, json!({"message": "Glossary not found"})),
+            _ => deepl_mock(r),
+        })
+        .await;
+        let req = glossary_request();
+        let translator = DeepLTranslator::new(&entry(&server));
+        let client = Client::new();
+        let texts = vec!["Tauri app".to_string()];
+
+        translator.translate_batch(&client, &req, &texts).await.unwrap();
+        let requests = server.requests();
+        let sent: Vec<_> = requests.iter().filter(|r| r.path == "/v2/translate").collect();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].json()["glossary_id"], "new-1");
+        // The retry protects the term instead
+        assert!(sent[1].json().get("glossary_id").is_none());
+        assert_eq!(sent[1].json()["text"][0], "{{0}} app");
+
+        // The next request makes a fresh glossary
+        translator.translate_batch(&client, &req, &texts).await.unwrap();
+        assert_eq!(created.load(Ordering::SeqCst), 2);
+        assert_eq!(server.requests().last().unwrap().json()["glossary_id"], "new-2");
+        assert_eq!(glossaries().created[&translator.account()], ["new-2"]);
+    }
+
+    #[test]
+    fn detects_markup() {
+        assert_eq!(tag_handling("<?xml version=\"1.0\"?><a/>"), Some("xml"));
+        assert_eq!(tag_handling("<p>Hello</p>"), Some("html"));
+        assert_eq!(tag_handling("a < b and c > d"), None);
+    }
+}
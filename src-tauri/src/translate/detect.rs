@@ -0,0 +1,495 @@
+use super::langmap;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+// ==================== Language Detection ====================
+
+/// Best guess for a text's language. `confidence` is in 0..=1: near 1 when the
+/// script alone decides it or the n-gram model is clearly ahead, low for short
+/// or mixed text.
+#[derive(Debug, Clone, Serialize)]
+pub struct Detection {
+    pub lang: &'static str,
+    pub confidence: f64,
+}
+
+#[derive(Default)]
+struct ScriptCounts {
+    han: f64,
+    kana: f64,
+    hangul: f64,
+    thai: f64,
+    devanagari: f64,
+    arabic: f64,
+    hebrew: f64,
+    greek: f64,
+    cyrillic: f64,
+    latin: f64,
+}
+
+/// CJK characters carry about a word each, so they count more than letters
+const CJK_WEIGHT: f64 = 3.0;
+
+fn count_scripts(text: &str) -> ScriptCounts {
+    let mut c = ScriptCounts::default();
+    for ch in text.chars() {
+        match ch {
+            '\u{4e00}'..='\u{9fff}' | '\u{3400}'..='\u{4dbf}' | '\u{f900}'..='\u{faff}' => {
+                c.han += CJK_WEIGHT
+            }
+            '\u{3040}'..='\u{309f}' | '\u{30a0}'..='\u{30ff}' | '\u{31f0}'..='\u{31ff}' => {
+                c.kana += CJK_WEIGHT
+            }
+            '\u{ac00}'..='\u{d7af}' | '\u{1100}'..='\u{11ff}' | '\u{3130}'..='\u{318f}' => {
+                c.hangul += CJK_WEIGHT
+            }
+            '\u{0e00}'..='\u{0e7f}' => c.thai += 1.0,
+            '\u{0900}'..='\u{097f}' => c.devanagari += 1.0,
+            '\u{0600}'..='\u{06ff}' | '\u{0750}'..='\u{077f}' => c.arabic += 1.0,
+            '\u{0590}'..='\u{05ff}' => c.hebrew += 1.0,
+            '\u{0370}'..='\u{03ff}' | '\u{1f00}'..='\u{1fff}' => c.greek += 1.0,
+            '\u{0400}'..='\u{04ff}' => c.cyrillic += 1.0,
+            ch if is_latin(ch) => c.latin += 1.0,
+            _ => {}
+        }
+    }
+    c
+}
+
+fn is_latin(c: char) -> bool {
+    c.is_ascii_alphabetic()
+        || (c.is_alphabetic() && matches!(c, '\u{00c0}'..='\u{024f}' | '\u{1e00}'..='\u{1eff}'))
+}
+
+/// Languages told apart by the n-gram model, per script
+const LATIN: &[&str] = &["en", "fr", "de", "es", "pt", "it", "nl", "pl", "tr", "vi", "id", "ms"];
+const CYRILLIC: &[&str] = &["ru", "uk"];
+
+/// Every code `detect` can return
+const DETECTABLE: &[&str] = &[
+    "en", "fr", "de", "es", "pt", "it", "nl", "pl", "tr", "vi", "id", "ms", "ru", "uk", "ja", "ko", "zh-CN",
+    "zh-TW", "th", "hi", "ar", "he", "el",
+];
+
+/// Highest confidence for a guess that may really be a catalog language the
+/// detector has no model for; below what is trusted as the source language
+const UNPROFILED_CONFIDENCE: f64 = 0.6;
+
+/// Share of the words that must occur in the winning Latin sample. Text in an
+/// unprofiled language (sv, cs, ro...) still lands on some profile, but hardly
+/// any of its words are in that profile's sample.
+const KNOWN_WORDS: f64 = 0.15;
+
+/// Detect the language of `text` among `DETECTABLE`. Languages in the catalog
+/// but not in that list come out as the closest detectable one, with at most
+/// `UNPROFILED_CONFIDENCE`: by script for non-Latin text (fa as ar, bg as ru),
+/// by vocabulary for Latin text.
+pub fn detect(text: &str) -> Detection {
+    let c = count_scripts(text);
+    let total = c.han
+        + c.kana
+        + c.hangul
+        + c.thai
+        + c.devanagari
+        + c.arabic
+        + c.hebrew
+        + c.greek
+        + c.cyrillic
+        + c.latin;
+    if total == 0.0 {
+        return Detection {
+            lang: "en",
+            confidence: 0.0,
+        };
+    }
+
+    // Japanese mixes kanji with kana; any real share of kana settles it
+    let cjk = c.han + c.kana;
+    if c.kana > 0.0 && c.kana / cjk > 0.05 && cjk >= c.hangul {
+        return script_only("ja", cjk / total);
+    }
+
+    let scripts = [
+        ("han", c.han),
+        ("ko", c.hangul),
+        ("th", c.thai),
+        ("hi", c.devanagari),
+        ("ar", c.arabic),
+        ("he", c.hebrew),
+        ("el", c.greek),
+        ("cyrillic", c.cyrillic),
+        ("latin", c.latin),
+    ];
+    let (script, count) = scripts
+        .iter()
+        .copied()
+        .fold(("latin", 0.0), |best, s| if s.1 > best.1 { s } else { best });
+    let share = count / total;
+
+    let detection = match script {
+        "han" => {
+            let (lang, variant_confidence) = chinese_variant(text);
+            Detection {
+                lang,
+                confidence: share * variant_confidence,
+            }
+        }
+        "cyrillic" => match cyrillic_variant(text) {
+            Some(lang) => script_only(lang, share),
+            None => classify(text, CYRILLIC, share),
+        },
+        "latin" => {
+            let detection = classify(text, LATIN, share);
+            if known_words(text, detection.lang) < KNOWN_WORDS {
+                return uncertain(detection);
+            }
+            return detection;
+        }
+        lang => script_only(lang, share),
+    };
+    if shares_script(detection.lang) {
+        uncertain(detection)
+    } else {
+        detection
+    }
+}
+
+fn script_only(lang: &'static str, share: f64) -> Detection {
+    Detection {
+        lang,
+        confidence: share,
+    }
+}
+
+fn uncertain(detection: Detection) -> Detection {
+    Detection {
+        confidence: detection.confidence.min(UNPROFILED_CONFIDENCE),
+        ..detection
+    }
+}
+
+/// Whether the catalog has languages in `lang`'s script that can't be detected
+/// (ur and fa share Arabic script with ar, mr and ne Devanagari with hi)
+fn shares_script(lang: &str) -> bool {
+    let languages = langmap::languages();
+    let Some(script) = languages.iter().find(|l| l.code == lang).map(|l| l.script) else {
+        return false;
+    };
+    languages
+        .iter()
+        .any(|l| l.script == script && !DETECTABLE.contains(&l.code))
+}
+
+/// Share of the words of `text` that occur in `lang`'s sample
+fn known_words(text: &str, lang: &str) -> f64 {
+    let Some(profile) = profiles().iter().find(|p| p.lang == lang) else {
+        return 0.0;
+    };
+    let words = words(text);
+    if words.is_empty() {
+        return 0.0;
+    }
+    let known = words.iter().filter(|w| profile.words.contains(*w)).count();
+    known as f64 / words.len() as f64
+}
+
+// ==================== Simplified / Traditional ====================
+
+/// Common characters that differ between the two scripts, pairwise aligned.
+/// Characters valid in both (后, 里, 干, 只...) are left out.
+const SIMPLIFIED: &str = "这个们来时说国会对为学过经现开关长问见东车书从动头电话气业进实与万义么几门间让认识语体点种两机难听写边爱钱飞马鱼鸟龙亲觉区医乐岁买卖网视总单应将华场图报员无处题变传许该论运达选连类级结给红约线组细终统热观计设证读谁调请谢历双还样发没吗钟园团风饭馆脑够办产厂广众优杂权条极标树欢汉灯爷独环画确礼离积稳笔紧纸练绍继续绿编罗联胜节药获虽装规订讨训议记讲访评诉词译试诗谈负责贵费转轻较辆远适邮钢铁银错闻队阳阴际陆险随须顾预领颜饮验鸡黄齐龄";
+const TRADITIONAL: &str = "這個們來時說國會對為學過經現開關長問見東車書從動頭電話氣業進實與萬義麼幾門間讓認識語體點種兩機難聽寫邊愛錢飛馬魚鳥龍親覺區醫樂歲買賣網視總單應將華場圖報員無處題變傳許該論運達選連類級結給紅約線組細終統熱觀計設證讀誰調請謝歷雙還樣發沒嗎鐘園團風飯館腦夠辦產廠廣眾優雜權條極標樹歡漢燈爺獨環畫確禮離積穩筆緊紙練紹繼續綠編羅聯勝節藥獲雖裝規訂討訓議記講訪評訴詞譯試詩談負責貴費轉輕較輛遠適郵鋼鐵銀錯聞隊陽陰際陸險隨須顧預領顏飲驗雞黃齊齡";
+
+/// zh-CN or zh-TW by which script's distinctive characters occur more,
+/// with how decisive that was
+fn chinese_variant(text: &str) -> (&'static str, f64) {
+    let simplified = text.chars().filter(|c| SIMPLIFIED.contains(*c)).count() as f64;
+    let traditional = text.chars().filter(|c| TRADITIONAL.contains(*c)).count() as f64;
+    if simplified + traditional == 0.0 {
+        // Nothing distinctive: most Han text is Simplified, but say so only tentatively
+        return ("zh-CN", 0.7);
+    }
+    let lang = if traditional > simplified { "zh-TW" } else { "zh-CN" };
+    let margin = (simplified - traditional).abs() / (simplified + traditional);
+    (lang, 0.5 + 0.5 * margin)
+}
+
+// ==================== Russian / Ukrainian ====================
+
+/// Letters only one of the two alphabets has
+const RUSSIAN_ONLY: &str = "ыэъё";
+const UKRAINIAN_ONLY: &str = "іїєґ";
+
+/// ru or uk when letters of only one alphabet occur; `None` leaves it to the n-grams
+fn cyrillic_variant(text: &str) -> Option<&'static str> {
+    let lower = text.to_lowercase();
+    let russian = lower.chars().any(|c| RUSSIAN_ONLY.contains(c));
+    let ukrainian = lower.chars().any(|c| UKRAINIAN_ONLY.contains(c));
+    match (russian, ukrainian) {
+        (true, false) => Some("ru"),
+        (false, true) => Some("uk"),
+        _ => None,
+    }
+}
+
+// ==================== N-gram Model ====================
+
+/// Additive smoothing and assumed vocabulary size for unseen n-grams
+const ALPHA: f64 = 0.5;
+const VOCABULARY: f64 = 4000.0;
+
+struct Profile {
+    lang: &'static str,
+    counts: HashMap<String, u32>,
+    total: f64,
+    /// Distinct words of the sample
+    words: HashSet<String>,
+}
+
+impl Profile {
+    fn log_prob(&self, gram: &str) -> f64 {
+        let count = self.counts.get(gram).copied().unwrap_or(0) as f64;
+        ((count + ALPHA) / (self.total + ALPHA * VOCABULARY)).ln()
+    }
+}
+
+/// Lowercased words
+fn words(text: &str) -> Vec<String> {
+    // Turkish İ would lowercase to "i" plus a combining dot and split the word
+    let lower = text.replace('İ', "i").to_lowercase();
+    lower
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Words padded with spaces, cut into 1- to 3-grams
+fn ngrams(text: &str) -> Vec<String> {
+    let mut grams = Vec::new();
+    for word in words(text) {
+        let chars: Vec<char> = format!(" {} ", word).chars().collect();
+        for n in 1..=3 {
+            for window in chars.windows(n) {
+                if n == 1 && window[0] == ' ' {
+                    continue;
+                }
+                grams.push(window.iter().collect());
+            }
+        }
+    }
+    grams
+}
+
+fn profiles() -> &'static [Profile] {
+    static PROFILES: OnceLock<Vec<Profile>> = OnceLock::new();
+    PROFILES.get_or_init(|| {
+        SAMPLES
+            .iter()
+            .map(|(lang, sample)| {
+                let mut counts = HashMap::new();
+                for gram in ngrams(sample) {
+                    *counts.entry(gram).or_insert(0) += 1;
+                }
+                let total = counts.values().sum::<u32>() as f64;
+                Profile {
+                    lang,
+                    counts,
+                    total,
+                    words: words(sample).into_iter().collect(),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Naive Bayes over the candidate languages. The posterior is tempered by the
+/// square root of the n-gram count, since the n-grams are far from independent
+/// and short inputs should not come out certain.
+fn classify(text: &str, candidates: &[&str], share: f64) -> Detection {
+    let grams = ngrams(text);
+    let scored: Vec<(&'static str, f64)> = profiles()
+        .iter()
+        .filter(|p| candidates.contains(&p.lang))
+        .map(|p| (p.lang, grams.iter().map(|g| p.log_prob(g)).sum()))
+        .collect();
+    let Some(&(best_lang, best)) = scored.iter().max_by(|a, b| a.1.total_cmp(&b.1)) else {
+        return Detection {
+            lang: "en",
+            confidence: 0.0,
+        };
+    };
+    let temper = (grams.len() as f64).sqrt().max(1.0);
+    let norm: f64 = scored.iter().map(|(_, s)| ((s - best) / temper).exp()).sum();
+    Detection {
+        lang: best_lang,
+        confidence: share / norm,
+    }
+}
+
+// ==================== Training Samples ====================
+
+/// Everyday text per language (greetings, plans, travel and shopping), rich in
+/// function words, from which the n-gram profiles are built on first use
+const SAMPLES: &[(&str, &str)] = &[
+    ("en", "Hello, how are you? Thank you very much for your help. Good morning, my name is John and I live in New York. All human beings are born free and equal in dignity and rights. They are endowed with reason and conscience and should act towards one another in a spirit of brotherhood. The quick brown fox jumps over the lazy dog. I don't know what you want to do tonight, but we could go to the cinema with our friends. It is a very nice day and the weather in the city is good. She said that she would come tomorrow because her brother is ill. What did you think of this book? The children are playing in the garden while their parents are making lunch. There are many things to see in London, especially the museums and the churches. I think that it will be better if we can find out where they are going and why they have not called us yet. Which of these would you like to read first? I want to buy a train ticket to the airport for tomorrow morning. How much does it cost? Could you tell me where the station is? We are staying at a small hotel near the river for three nights. Can I pay by card or only with cash? The shop opens at nine and closes at six in the evening. Excuse me, is this seat free? My phone is not working and I need to call my wife."),
+    ("fr", "Bonjour, comment ça va ? Merci beaucoup pour votre aide. Je m'appelle Pierre et j'habite à Lyon. Tous les êtres humains naissent libres et égaux en dignité et en droits. Ils sont doués de raison et de conscience et doivent agir les uns envers les autres dans un esprit de fraternité. Je ne sais pas ce que tu veux faire ce soir, mais nous pourrions aller au cinéma avec nos amis. C'est une très belle journée et il fait beau dans la ville. Elle a dit qu'elle viendrait demain parce que son frère est malade. Qu'est-ce que vous avez pensé de ce livre ? Les enfants jouent dans le jardin pendant que leurs parents préparent le déjeuner. Il y a beaucoup de choses à voir à Paris, surtout les musées et les églises. Je pense qu'il vaudrait mieux savoir où ils vont et pourquoi ils ne nous ont pas encore appelés. Je veux acheter un billet de train pour l'aéroport demain matin. Combien ça coûte ? Pourriez-vous me dire où se trouve la gare ? Nous restons trois nuits dans un petit hôtel près de la rivière. Est-ce que je peux payer par carte ou seulement en espèces ? Le magasin ouvre à neuf heures et ferme à six heures du soir. Excusez-moi, cette place est-elle libre ? Mon téléphone ne marche pas et je dois appeler ma femme."),
+    ("de", "Hallo, wie geht's? Vielen Dank für Ihre Hilfe. Ich heiße Peter und wohne in Berlin. Alle Menschen sind frei und gleich an Würde und Rechten geboren. Sie sind mit Vernunft und Gewissen begabt und sollen einander im Geist der Brüderlichkeit begegnen. Ich weiß nicht, was du heute Abend machen möchtest, aber wir könnten mit unseren Freunden ins Kino gehen. Es ist ein sehr schöner Tag und das Wetter in der Stadt ist gut. Sie hat gesagt, dass sie morgen kommt, weil ihr Bruder krank ist. Was haben Sie über dieses Buch gedacht? Die Kinder spielen im Garten, während ihre Eltern das Mittagessen vorbereiten. Es gibt viele Dinge zu sehen, besonders die Museen und die Kirchen. Ich glaube, es wäre besser, wenn wir herausfinden, wohin sie gehen und warum sie uns noch nicht angerufen haben. Ich will morgen früh eine Fahrkarte zum Flughafen kaufen. Wie viel kostet das? Können Sie mir sagen, wo der Bahnhof ist? Wir wohnen drei Nächte in einem kleinen Hotel in der Nähe des Flusses. Kann ich mit Karte bezahlen oder nur bar? Das Geschäft öffnet um neun Uhr und schließt um sechs Uhr abends. Entschuldigung, ist dieser Platz frei? Mein Handy funktioniert nicht und ich muss meine Frau anrufen."),
+    ("es", "Hola, ¿qué tal? Muchas gracias por tu ayuda. Me llamo Carlos y vivo en Sevilla. Todos los seres humanos nacen libres e iguales en dignidad y derechos y, dotados como están de razón y conciencia, deben comportarse fraternalmente los unos con los otros. No sé qué quieres hacer esta noche, pero podríamos ir al cine con nuestros amigos. Es un día muy bonito y hace buen tiempo en la ciudad. Ella dijo que vendría mañana porque su hermano está enfermo. ¿Qué pensaste de este libro? Los niños juegan en el jardín mientras sus padres preparan la comida. Hay muchas cosas que ver en España, sobre todo los museos y las iglesias. Creo que sería mejor si pudiéramos saber adónde van y por qué todavía no nos han llamado. Quiero comprar un billete de tren para el aeropuerto mañana por la mañana. ¿Cuánto cuesta? ¿Me podría decir dónde está la estación? Nos quedamos tres noches en un hotel pequeño cerca del río. ¿Puedo pagar con tarjeta o solo en efectivo? La tienda abre a las nueve y cierra a las seis de la tarde. Perdone, ¿está libre este asiento? Mi teléfono no funciona y tengo que llamar a mi mujer."),
+    ("pt", "Olá, tudo bem? Muito obrigado pela sua ajuda. Meu nome é João e eu moro no Brasil. Todos os seres humanos nascem livres e iguais em dignidade e em direitos. Dotados de razão e de consciência, devem agir uns para com os outros em espírito de fraternidade. Não sei o que você quer fazer hoje à noite, mas nós poderíamos ir ao cinema com os nossos amigos. É um dia muito bonito e o tempo está bom na cidade. Ela disse que viria amanhã porque o irmão dela está doente. O que você achou deste livro? As crianças brincam no jardim enquanto os pais preparam o almoço. Há muitas coisas para ver em Portugal, sobretudo os museus e as igrejas. Acho que seria melhor se conseguíssemos descobrir para onde eles vão e por que ainda não nos ligaram. Quero comprar uma passagem de trem para o aeroporto amanhã de manhã. Quanto custa? Você poderia me dizer onde fica a estação? Vamos ficar três noites num hotel pequeno perto do rio. Posso pagar com cartão ou só em dinheiro? A loja abre às nove e fecha às seis da tarde. Com licença, este lugar está livre? O meu telefone não está funcionando e eu preciso ligar para a minha mulher."),
+    ("it", "Ciao, come va? Grazie mille per il tuo aiuto. Mi chiamo Luca e vivo a Milano. Tutti gli esseri umani nascono liberi ed eguali in dignità e diritti. Essi sono dotati di ragione e di coscienza e devono agire gli uni verso gli altri in spirito di fratellanza. Non so che cosa vuoi fare stasera, ma potremmo andare al cinema con i nostri amici. È una giornata molto bella e fa bel tempo in città. Lei ha detto che verrà domani perché suo fratello è malato. Che cosa ne pensi di questo libro? I bambini giocano nel giardino mentre i loro genitori preparano il pranzo. Ci sono molte cose da vedere in Italia, soprattutto i musei e le chiese. Penso che sarebbe meglio se riuscissimo a sapere dove stanno andando e perché non ci hanno ancora chiamato. Voglio comprare un biglietto del treno per l'aeroporto domani mattina. Quanto costa? Mi potrebbe dire dov'è la stazione? Restiamo tre notti in un piccolo albergo vicino al fiume. Posso pagare con la carta o solo in contanti? Il negozio apre alle nove e chiude alle sei di sera. Scusi, questo posto è libero? Il mio telefono non funziona e devo chiamare mia moglie."),
+    ("nl", "Hallo, hoe is het? Heel erg bedankt voor je hulp. Mijn naam is Jan en ik woon in Utrecht. Alle mensen worden vrij en gelijk in waardigheid en rechten geboren. Zij zijn begiftigd met verstand en geweten, en behoren zich jegens elkander in een geest van broederschap te gedragen. Ik weet niet wat je vanavond wilt doen, maar we zouden met onze vrienden naar de bioscoop kunnen gaan. Het is een heel mooie dag en het weer in de stad is goed. Zij zei dat ze morgen komt omdat haar broer ziek is. Wat vond je van dit boek? De kinderen spelen in de tuin terwijl hun ouders het middageten klaarmaken. Er zijn veel dingen te zien in Nederland, vooral de musea en de kerken. Ik denk dat het beter is als we kunnen uitzoeken waar ze heen gaan en waarom ze ons nog niet hebben gebeld. Ik wil morgenochtend een treinkaartje naar het vliegveld kopen. Hoeveel kost dat? Kunt u mij zeggen waar het station is? We blijven drie nachten in een klein hotel bij de rivier. Kan ik met de kaart betalen of alleen contant? De winkel gaat om negen uur open en sluit om zes uur 's avonds. Pardon, is deze plaats vrij? Mijn telefoon doet het niet en ik moet mijn vrouw bellen."),
+    ("pl", "Dzień dobry, co słychać? Bardzo dziękuję za pomoc. Nazywam się Jan i mieszkam w Krakowie. Wszyscy ludzie rodzą się wolni i równi pod względem swej godności i swych praw. Są oni obdarzeni rozumem i sumieniem i powinni postępować wobec innych w duchu braterstwa. Nie wiem, co chcesz dziś wieczorem robić, ale moglibyśmy pójść do kina z naszymi przyjaciółmi. To jest bardzo piękny dzień i w mieście jest ładna pogoda. Ona powiedziała, że przyjdzie jutro, ponieważ jej brat jest chory. Co myślisz o tej książce? Dzieci bawią się w ogrodzie, a ich rodzice przygotowują obiad. W Polsce jest wiele rzeczy do zobaczenia, szczególnie muzea i kościoły. Myślę, że byłoby lepiej, gdybyśmy dowiedzieli się, dokąd idą i dlaczego jeszcze do nas nie zadzwonili. Chcę kupić bilet na pociąg na lotnisko jutro rano. Ile to kosztuje? Czy może mi pan powiedzieć, gdzie jest dworzec? Zostajemy trzy noce w małym hotelu niedaleko rzeki. Czy mogę zapłacić kartą, czy tylko gotówką? Sklep jest otwarty od dziewiątej do szóstej wieczorem. Przepraszam, czy to miejsce jest wolne? Mój telefon nie działa i muszę zadzwonić do żony."),
+    ("tr", "Merhaba, nasılsınız? Yardımınız için çok teşekkürler. Benim adım Ahmet ve Ankara'da yaşıyorum. Ben de iyiyim, sağ ol. Bütün insanlar hür, haysiyet ve haklar bakımından eşit doğarlar. Akıl ve vicdana sahiptirler ve birbirlerine karşı kardeşlik zihniyeti ile hareket etmelidirler. Bu akşam ne yapmak istediğini bilmiyorum, ama arkadaşlarımızla sinemaya gidebiliriz. Çok güzel bir gün ve şehirde hava çok iyi. Kardeşi hasta olduğu için yarın geleceğini söyledi. Bu kitap hakkında ne düşündün? Çocuklar bahçede oynuyor, anne ve babaları da öğle yemeğini hazırlıyor. Türkiye'de görülecek çok şey var, özellikle müzeler ve camiler. Bence nereye gittiklerini ve neden bizi henüz aramadıklarını öğrenebilirsek daha iyi olur. Yarın sabah havalimanına bir tren bileti almak istiyorum. Bu ne kadar? Bana istasyonun nerede olduğunu söyleyebilir misiniz? Nehrin yakınındaki küçük bir otelde üç gece kalıyoruz. Kartla ödeyebilir miyim, yoksa sadece nakit mi? Mağaza saat dokuzda açılıyor ve akşam altıda kapanıyor. Affedersiniz, bu koltuk boş mu? Telefonum çalışmıyor ve karımı aramam gerekiyor."),
+    ("vi", "Xin chào, dạo này thế nào? Cảm ơn bạn đã giúp đỡ. Tên tôi là Minh và tôi sống ở Sài Gòn. Tất cả mọi người sinh ra đều được tự do và bình đẳng về nhân phẩm và quyền lợi. Mọi con người đều được tạo hóa ban cho lý trí và lương tâm và cần phải đối xử với nhau trong tình bằng hữu. Tôi không biết bạn muốn làm gì tối nay, nhưng chúng ta có thể đi xem phim với bạn bè. Hôm nay là một ngày rất đẹp và thời tiết trong thành phố rất tốt. Cô ấy nói rằng ngày mai cô ấy sẽ đến vì anh trai của cô ấy bị ốm. Bạn nghĩ gì về cuốn sách này? Những đứa trẻ đang chơi trong vườn trong khi cha mẹ chúng chuẩn bị bữa trưa. Có rất nhiều thứ để xem ở Việt Nam, đặc biệt là các bảo tàng và những ngôi chùa. Tôi nghĩ sẽ tốt hơn nếu chúng ta biết họ đi đâu và tại sao họ chưa gọi cho chúng ta. Tôi muốn mua một vé tàu đi sân bay vào sáng mai. Cái này giá bao nhiêu? Bạn có thể cho tôi biết nhà ga ở đâu không? Chúng tôi ở ba đêm trong một khách sạn nhỏ gần bờ sông. Tôi có thể trả bằng thẻ hay chỉ bằng tiền mặt? Cửa hàng mở cửa lúc chín giờ và đóng cửa lúc sáu giờ tối. Xin lỗi, chỗ này còn trống không? Điện thoại của tôi bị hỏng và tôi cần gọi cho vợ tôi."),
+    ("id", "Halo, apa kabar? Terima kasih atas bantuannya. Nama saya Budi dan saya tinggal di Bandung, kantor saya dekat rumah. Semua orang dilahirkan merdeka dan mempunyai martabat dan hak-hak yang sama. Mereka dikaruniai akal dan hati nurani dan hendaknya bergaul satu sama lain dalam semangat persaudaraan. Saya tidak tahu apa yang ingin kamu lakukan malam ini, tapi kita bisa pergi ke bioskop bersama teman-teman. Hari ini sangat indah dan cuacanya bagus di kota. Dia bilang dia akan datang besok karena kakaknya sedang sakit. Apa pendapatmu tentang buku ini? Anak-anak bermain di kebun sementara orang tua mereka menyiapkan makan siang. Ada banyak hal yang bisa dilihat di Indonesia, terutama museum dan gereja. Pemerintah sudah memberikan uang itu kepada masyarakat karena mereka belum bisa bekerja. Saya kira lebih baik kalau kita bisa mencari tahu ke mana mereka pergi dan kenapa mereka belum menelepon kita. Saya mau membeli tiket kereta api ke bandara besok pagi. Berapa harganya? Bisakah Anda memberi tahu saya di mana stasiunnya? Kami menginap tiga malam di hotel kecil dekat sungai. Apakah saya bisa membayar dengan kartu atau hanya dengan uang tunai? Tokonya buka jam sembilan dan tutup jam enam sore. Permisi, apakah kursi ini kosong? Ponsel saya rusak dan saya harus menelepon istri saya."),
+    ("ms", "Helo, apa khabar? Terima kasih kerana membantu. Nama saya Ali dan saya tinggal di Johor, pejabat saya dekat rumah. Semua manusia dilahirkan bebas dan samarata dari segi kemuliaan dan hak-hak. Mereka mempunyai pemikiran dan perasaan hati dan hendaklah bertindak di antara satu sama lain dengan semangat persaudaraan. Saya tidak tahu apa yang awak mahu buat malam ini, tetapi kita boleh pergi ke pawagam bersama kawan-kawan. Hari ini sangat cantik dan cuaca di bandar ini baik. Dia kata dia akan datang esok kerana abangnya sedang sakit. Apakah pendapat awak tentang buku ini? Kanak-kanak bermain di taman manakala ibu bapa mereka menyediakan makan tengah hari. Terdapat banyak perkara yang boleh dilihat di Malaysia, terutamanya muzium dan masjid. Kerajaan telah memberikan wang itu kepada rakyat kerana mereka belum boleh bekerja. Saya rasa lebih baik jika kita dapat mengetahui ke mana mereka pergi dan mengapa mereka masih belum menelefon kita. Saya mahu membeli tiket tren ke lapangan terbang esok pagi. Berapa harganya? Boleh encik beritahu saya di mana stesen itu? Kami menginap tiga malam di sebuah hotel kecil berhampiran sungai. Bolehkah saya membayar dengan kad atau hanya dengan wang tunai? Kedai itu dibuka pada pukul sembilan dan ditutup pada pukul enam petang. Maaf, adakah tempat duduk ini kosong? Telefon bimbit saya rosak dan saya perlu menelefon isteri saya."),
+    ("ru", "Здравствуйте, как поживаете? Большое спасибо за помощь. Меня зовут Иван, и я живу в Петербурге. Все люди рождаются свободными и равными в своем достоинстве и правах. Они наделены разумом и совестью и должны поступать в отношении друг друга в духе братства. Я не знаю, что ты хочешь делать сегодня вечером, но мы могли бы пойти в кино с нашими друзьями. Сегодня очень красивый день, и в городе хорошая погода. Она сказала, что придёт завтра, потому что её брат болен. Что ты думаешь об этой книге? Дети играют в саду, пока их родители готовят обед. В России есть много интересного, особенно музеи и церкви. Я думаю, что было бы лучше, если бы мы узнали, куда они идут и почему они ещё не позвонили нам. Я хочу купить билет на поезд до аэропорта на завтра утром. Сколько это стоит? Не могли бы вы сказать, где находится вокзал? Мы остановимся на три ночи в маленькой гостинице у реки. Можно заплатить картой или только наличными? Магазин открывается в девять и закрывается в шесть вечера. Извините, это место свободно? Мой телефон не работает, и мне нужно позвонить жене."),
+    ("uk", "Добрий день, як ся маєте? Щиро дякую за допомогу. Мене звати Іван, і я живу у Львові. Всі люди народжуються вільними і рівними у своїй гідності та правах. Вони наділені розумом і совістю і повинні діяти у відношенні один до одного в дусі братерства. Я не знаю, що ти хочеш робити сьогодні ввечері, але ми могли б піти в кіно з нашими друзями. Сьогодні дуже гарний день, і в місті гарна погода. Вона сказала, що прийде завтра, бо її брат хворіє. Що ти думаєш про цю книжку? Діти граються в саду, поки їхні батьки готують обід. В Україні є багато цікавого, особливо музеї та церкви. Я думаю, що було б краще, якби ми дізналися, куди вони йдуть і чому вони ще не зателефонували нам. Я хочу купити квиток на поїзд до аеропорту на завтра вранці. Скільки це коштує? Чи не могли б ви сказати, де знаходиться вокзал? Ми зупинимося на три ночі в маленькому готелі біля річки. Можна заплатити карткою чи тільки готівкою? Магазин відчиняється о дев'ятій і зачиняється о шостій вечора. Вибачте, це місце вільне? Мій телефон не працює, і мені треба зателефонувати дружині."),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (text, language, whether the guess may be close)
+    const CASES: &[(&str, &str, bool)] = &[
+        ("I would like to book a table for two tonight.", "en", false),
+        ("Je voudrais réserver une table pour deux ce soir.", "fr", false),
+        ("Ich möchte heute Abend einen Tisch für zwei reservieren.", "de", false),
+        ("Quisiera reservar una mesa para dos esta noche.", "es", false),
+        ("Eu gostaria de reservar uma mesa para dois esta noite.", "pt", false),
+        ("Vorrei prenotare un tavolo per due stasera.", "it", false),
+        ("Ik wil graag een tafel voor twee reserveren vanavond.", "nl", false),
+        ("Chciałbym zarezerwować stolik dla dwóch osób na dziś wieczór.", "pl", false),
+        ("Bu akşam iki kişilik bir masa ayırtmak istiyorum.", "tr", false),
+        ("Tôi muốn đặt một bàn cho hai người tối nay.", "vi", false),
+        ("Saya ingin membeli tiket kereta ke Jakarta besok pagi.", "id", true),
+        ("Saya mahu membeli tiket bas ke Kuala Lumpur esok pagi.", "ms", true),
+        ("Я хочу купить билет на поезд.", "ru", false),
+        ("Я хочу купити квиток на потяг.", "uk", true),
+        ("Мы были дома весь вечер.", "ru", false),
+        ("Я живу у Львові і працюю вдома.", "uk", false),
+        ("明日の朝、東京へ行きます。", "ja", false),
+        ("내일 아침에 서울에 갑니다.", "ko", false),
+        ("我明天早上去北京开会。", "zh-CN", false),
+        ("我明天早上去台北開會。", "zh-TW", false),
+        ("這個問題很難", "zh-TW", false),
+        ("我在北京", "zh-CN", true),
+        ("พรุ่งนี้เช้าฉันจะไปกรุงเทพ", "th", false),
+        ("मैं कल सुबह दिल्ली जाऊँगा।", "hi", false),
+        ("سأذهب إلى القاهرة صباح الغد.", "ar", false),
+        ("אני נוסע לירושלים מחר בבוקר.", "he", false),
+        ("Αύριο το πρωί θα πάω στην Αθήνα.", "el", false),
+    ];
+
+    #[test]
+    fn detects_short_sentences() {
+        for (text, lang, _) in CASES {
+            assert_eq!(detect(text).lang, *lang, "{}", text);
+        }
+    }
+
+    #[test]
+    fn close_guesses_are_not_trusted() {
+        for (text, _, _) in CASES.iter().filter(|c| c.2) {
+            let confidence = detect(text).confidence;
+            assert!(confidence < crate::TRUSTED_DETECTION, "{} at {}", text, confidence);
+        }
+    }
+
+    /// Sentences about a topic none of the samples covers, so the words are
+    /// mostly new to the profiles
+    const HELD_OUT: &[(&str, &str)] = &[
+        ("en", "My grandmother keeps three old cats and bakes fresh bread every Sunday."),
+        ("fr", "Ma grand-mère garde trois vieux chats et fait du pain frais chaque dimanche."),
+        ("de", "Meine Großmutter hat drei alte Katzen und backt jeden Sonntag frisches Brot."),
+        ("es", "Mi abuela tiene tres gatos viejos y hornea pan fresco todos los domingos."),
+        ("pt", "A minha avó tem três gatos velhos e faz pão fresco todos os domingos."),
+        ("it", "Mia nonna ha tre vecchi gatti e cuoce il pane fresco ogni domenica."),
+        ("nl", "Mijn oma heeft drie oude katten en bakt elke zondag vers brood."),
+        ("pl", "Moja babcia ma trzy stare koty i w każdą niedzielę piecze świeży chleb."),
+        ("tr", "Büyükannemin üç yaşlı kedisi var ve her pazar taze ekmek pişiriyor."),
+        ("vi", "Bà tôi nuôi ba con mèo già và nướng bánh mì mỗi chủ nhật."),
+        ("id", "Nenek saya memelihara tiga ekor kucing tua dan membuat roti setiap hari Minggu."),
+        ("ms", "Nenek saya mahu membela tiga ekor kucing kerana dia sangat suka haiwan."),
+        ("ru", "Моя бабушка печет хлеб каждое воскресенье."),
+        ("uk", "Моя бабуся тримає трьох старих котів і щонеділі пече свіжий хліб."),
+    ];
+
+    #[test]
+    fn generalizes_beyond_the_samples() {
+        for (lang, text) in HELD_OUT {
+            let words = words(text);
+            let sample = SAMPLES.iter().find(|s| s.0 == *lang).unwrap().1.to_lowercase();
+            // Not lifted from the sample
+            assert!(!sample.contains(&words[..3].join(" ")), "{}", text);
+            assert_eq!(detect(text).lang, *lang, "{}", text);
+        }
+    }
+
+    #[test]
+    fn clear_latin_text_is_trusted() {
+        for (lang, text) in HELD_OUT.iter().filter(|(lang, _)| !["id", "ms", "ru", "uk"].contains(lang)) {
+            let confidence = detect(text).confidence;
+            assert!(confidence >= crate::TRUSTED_DETECTION, "{} at {}", lang, confidence);
+        }
+    }
+
+    /// Catalog languages without a profile
+    const UNPROFILED: &[(&str, &str)] = &[
+        ("sv", "Min mormor har tre gamla katter och bakar färskt bröd varje söndag."),
+        ("sv", "Jag vill gärna boka ett bord för två personer i kväll."),
+        ("da", "Min mormor har tre gamle katte og bager friskt brød hver søndag."),
+        ("no", "Bestemoren min har tre gamle katter og baker ferskt brød hver søndag."),
+        ("fi", "Isoäitini pitää kolmea vanhaa kissaa ja leipoo tuoretta leipää joka sunnuntai."),
+        ("cs", "Moje babička má tři staré kočky a každou neděli peče čerstvý chléb."),
+        ("ro", "Bunica mea are trei pisici bătrâne și coace pâine proaspătă în fiecare duminică."),
+        ("hu", "A nagymamámnak három öreg macskája van, és minden vasárnap friss kenyeret süt."),
+        ("sw", "Bibi yangu ana paka watatu wazee na huoka mkate kila Jumapili."),
+        ("tl", "Ang lola ko ay may tatlong matandang pusa at nagluluto ng tinapay tuwing Linggo."),
+        ("ca", "La meva àvia té tres gats vells i fa pa fresc cada diumenge."),
+        ("bg", "Баба ми има три стари котки и всяка неделя пече пресен хляб."),
+        ("sr", "Моја бака има три старе мачке и сваке недеље пече свеж хлеб."),
+        ("kk", "Менің әжемнің үш кәрі мысығы бар."),
+        ("fa", "مادربزرگ من سه گربه پیر دارد و هر یکشنبه نان تازه می‌پزد."),
+        ("ur", "میری دادی کے پاس تین بوڑھی بلیاں ہیں اور وہ ہر اتوار تازہ روٹی پکاتی ہیں۔"),
+        ("mr", "माझ्या आजीकडे तीन म्हाताऱ्या मांजरी आहेत."),
+        ("ne", "मेरो हजुरआमासँग तीनवटा बूढा बिरालाहरू छन्।"),
+        ("yi", "מײַן באָבע האָט דרײַ אַלטע קעץ."),
+        ("yue", "我婆婆養咗三隻老貓，佢逢星期日都焗麵包。"),
+    ];
+
+    #[test]
+    fn unprofiled_languages_are_not_trusted() {
+        for (lang, text) in UNPROFILED {
+            let detection = detect(text);
+            assert!(
+                detection.confidence < crate::TRUSTED_DETECTION,
+                "{} taken for {} at {}",
+                lang,
+                detection.lang,
+                detection.confidence
+            );
+        }
+    }
+
+    #[test]
+    fn only_scripts_with_unprofiled_languages_are_capped() {
+        assert!(shares_script("ar"));
+        assert!(shares_script("hi"));
+        assert!(shares_script("ru"));
+        assert!(shares_script("he"));
+        assert!(!shares_script("ja"));
+        assert!(!shares_script("ko"));
+        assert!(!shares_script("th"));
+        assert!(!shares_script("el"));
+        assert!(detect("Αύριο το πρωί θα πάω στην Αθήνα.").confidence >= crate::TRUSTED_DETECTION);
+    }
+
+    #[test]
+    fn empty_text_has_no_confidence() {
+        assert_eq!(detect("123 !?").confidence, 0.0);
+    }
+}
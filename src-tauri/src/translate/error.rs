@@ -0,0 +1,151 @@
+use reqwest::{Response, StatusCode};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+
+/// Why a translator failed. Serialized as `{"kind": "...", ...}` so the UI can
+/// show specific guidance, and retries can decide based on the variant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TranslateError {
+    /// API key / app id missing from the config
+    NotConfigured,
+    Network { message: String },
+    Timeout,
+    /// Non-success status that doesn't map to a more specific kind
+    Http { status: u16, message: String },
+    /// Credentials rejected
+    Auth { message: String },
+    /// Account quota or balance exhausted
+    Quota { message: String },
+    /// Too many requests; `retry_after` in seconds when the provider says
+    RateLimit { retry_after: Option<u64> },
+    Parse { message: String },
+    UnsupportedLanguage { lang: String },
+    /// Provider-specific error code that doesn't fit the kinds above
+    Provider { code: String, message: String },
+    /// Failure inside the app itself (e.g. a panicked task)
+    Internal { message: String },
+}
+
+impl fmt::Display for TranslateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TranslateError::NotConfigured => write!(f, "API key not configured"),
+            TranslateError::Network { message } => write!(f, "Network error: {}", message),
+            TranslateError::Timeout => write!(f, "Request timed out"),
+            TranslateError::Http { status, message } => write!(f, "HTTP {}: {}", status, message),
+            TranslateError::Auth { message } => write!(f, "Authentication failed: {}", message),
+            TranslateError::Quota { message } => write!(f, "Quota exceeded: {}", message),
+            TranslateError::RateLimit { retry_after: Some(secs) } => {
+                write!(f, "Rate limited, retry after {}s", secs)
+            }
+            TranslateError::RateLimit { retry_after: None } => write!(f, "Rate limited"),
+            TranslateError::Parse { message } => write!(f, "Parse error: {}", message),
+            TranslateError::UnsupportedLanguage { lang } => write!(f, "Unsupported language: {}", lang),
+            TranslateError::Provider { code, message } if code.is_empty() => write!(f, "{}", message),
+            TranslateError::Provider { code, message } => write!(f, "Error {}: {}", code, message),
+            TranslateError::Internal { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<reqwest::Error> for TranslateError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            TranslateError::Timeout
+        } else if e.is_decode() {
+            TranslateError::Parse {
+                message: e.to_string(),
+            }
+        } else {
+            TranslateError::Network {
+                message: e.to_string(),
+            }
+        }
+    }
+}
+
+impl From<serde_json::Error> for TranslateError {
+    fn from(e: serde_json::Error) -> Self {
+        TranslateError::Parse {
+            message: e.to_string(),
+        }
+    }
+}
+
+impl TranslateError {
+    /// Transient failures worth another attempt
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            TranslateError::Network { .. }
+            | TranslateError::Timeout
+            | TranslateError::RateLimit { .. } => true,
+            TranslateError::Http { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Map an HTTP error status to the closest kind
+    pub fn from_status(status: StatusCode, message: String, retry_after: Option<u64>) -> Self {
+        match status.as_u16() {
+            401 | 403 => TranslateError::Auth { message },
+            // DeepL uses 456 for "quota exceeded"
+            402 | 456 => TranslateError::Quota { message },
+            429 => TranslateError::RateLimit { retry_after },
+            408 | 504 => TranslateError::Timeout,
+            code => TranslateError::Http {
+                status: code,
+                message,
+            },
+        }
+    }
+}
+
+/// Pass successful responses through; turn anything else into a typed error,
+/// using the body as the message and honouring `Retry-After`
+pub async fn check_status(resp: Response) -> Result<Response, TranslateError> {
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+    let retry_after = resp
+        .headers()
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok());
+    let body = resp.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|json| {
+            json["error"]["message"]
+                .as_str()
+                .or_else(|| json["message"].as_str())
+                .or_else(|| json["error"].as_str())
+                .map(|s| s.to_string())
+        })
+        .unwrap_or_else(|| body.chars().take(200).collect());
+    // OpenAI-compatible APIs report an exhausted balance as a 429
+    if status == StatusCode::TOO_MANY_REQUESTS && body.contains("insufficient_quota") {
+        return Err(TranslateError::Quota { message });
+    }
+    Err(TranslateError::from_status(status, message, retry_after))
+}
+
+/// Accept both the typed form and the plain strings stored by older versions
+/// (history written before errors were typed)
+pub fn deserialize_error<'de, D: Deserializer<'de>>(d: D) -> Result<Option<TranslateError>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Typed(TranslateError),
+        Legacy(String),
+    }
+    Ok(match Option::<Repr>::deserialize(d)? {
+        Some(Repr::Typed(e)) => Some(e),
+        Some(Repr::Legacy(message)) => Some(TranslateError::Provider {
+            code: String::new(),
+            message,
+        }),
+        None => None,
+    })
+}
@@ -0,0 +1,293 @@
+use super::sse::SseParser;
+use super::{
+    check_status, langmap, prompt, Capabilities, DeltaSink, StreamDelta, TranslateError, TranslateRequest,
+    TranslateResult, Translator,
+};
+use crate::config::LlmServiceEntry;
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reqwest::{Client, Response};
+
+// ==================== Google Gemini ====================
+
+pub struct GeminiTranslator {
+    entry: LlmServiceEntry,
+}
+
+/// Text of the first candidate, or why the model returned none
+fn candidate_text(json: &serde_json::Value) -> Result<String, TranslateError> {
+    if let Some(reason) = json["promptFeedback"]["blockReason"].as_str() {
+        return Err(TranslateError::Provider {
+            code: reason.to_string(),
+            message: "prompt blocked".to_string(),
+        });
+    }
+    let candidate = &json["candidates"][0];
+    let text: String = candidate["content"]["parts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|p| p["text"].as_str())
+        .collect();
+    match candidate["finishReason"].as_str() {
+        Some(reason @ ("SAFETY" | "RECITATION" | "PROHIBITED_CONTENT" | "BLOCKLIST")) if text.is_empty() => {
+            Err(TranslateError::Provider {
+                code: reason.to_string(),
+                message: "response blocked".to_string(),
+            })
+        }
+        _ => Ok(text),
+    }
+}
+
+impl GeminiTranslator {
+    pub fn new(entry: &LlmServiceEntry) -> Self {
+        Self { entry: entry.clone() }
+    }
+
+    /// Read a `streamGenerateContent?alt=sse` response, forwarding each chunk's text
+    async fn read_stream(&self, resp: Response, sink: &DeltaSink) -> Result<String, TranslateError> {
+        let mut body = resp.bytes_stream();
+        let mut parser = SseParser::default();
+        let mut translated = String::new();
+
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk?;
+            for data in parser.feed(&chunk) {
+                let json: serde_json::Value = serde_json::from_str(&data)?;
+                if let Some(msg) = json["error"]["message"].as_str() {
+                    return Err(TranslateError::Provider {
+                        code: json["error"]["status"].as_str().unwrap_or("").to_string(),
+                        message: msg.to_string(),
+                    });
+                }
+                let delta = candidate_text(&json)?;
+                if delta.is_empty() {
+                    continue;
+                }
+                translated.push_str(&delta);
+                sink(StreamDelta {
+                    service: self.name(),
+                    delta,
+                });
+            }
+        }
+        Ok(translated)
+    }
+}
+
+#[async_trait]
+impl Translator for GeminiTranslator {
+    fn id(&self) -> &'static str {
+        "gemini"
+    }
+
+    fn name(&self) -> String {
+        format!("Gemini ({})", self.entry.model)
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_credentials: true,
+            detects_language: false,
+            streaming: true,
+            dictionary: false,
+            glossary: true,
+            max_chars: 2000,
+            max_batch: 1,
+            prompt_modes: true,
+        }
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        let entry = &self.entry;
+        if entry.api_key.is_empty() || entry.model.is_empty() {
+            return Err(TranslateError::NotConfigured);
+        }
+
+        let (source, target_desc) = langmap::map_pair(self.id(), req)?;
+        let source_desc = source.unwrap_or("auto-detected language");
+        let (system_prompt, user_prompt) = prompt::render(&req.prompt, source_desc, target_desc, req);
+
+        let body = serde_json::json!({
+            "systemInstruction": {"parts": [{"text": system_prompt}]},
+            "contents": [{"role": "user", "parts": [{"text": user_prompt}]}],
+            "generationConfig": {"temperature": entry.temperature, "maxOutputTokens": entry.max_tokens}
+        });
+        let base = entry.api_url.trim_end_matches('/');
+        let url = match req.on_delta {
+            Some(_) => format!("{}/models/{}:streamGenerateContent?alt=sse", base, entry.model),
+            None => format!("{}/models/{}:generateContent", base, entry.model),
+        };
+
+        let resp = client
+            .post(url)
+            .header("x-goog-api-key", &entry.api_key)
+            .json(&body)
+            .send()
+            .await?;
+        // An invalid key comes back as a plain 400 INVALID_ARGUMENT
+        let resp = check_status(resp).await.map_err(|e| match e {
+            TranslateError::Http { status: 400, message } if message.contains("API key") => {
+                TranslateError::Auth { message }
+            }
+            other => other,
+        })?;
+
+        let translated = match &req.on_delta {
+            Some(sink) => self.read_stream(resp, sink).await?,
+            None => candidate_text(&resp.json::<serde_json::Value>().await?)?,
+        };
+        Ok(TranslateResult::ok(
+            self.name(),
+            translated.trim().to_string(),
+            req.source.clone(),
+            &req.target,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock::{self, MockServer, Reply};
+    use serde_json::json;
+
+    fn entry(server: &MockServer) -> LlmServiceEntry {
+        LlmServiceEntry {
+            enabled: true,
+            api_key: "gm-test".to_string(),
+            api_url: format!("{}/v1beta/", server.url),
+            model: "test-model".to_string(),
+            temperature: 0.3,
+            max_tokens: 1024,
+        }
+    }
+
+    fn candidate(text: &str) -> serde_json::Value {
+        json!({"candidates": [{"content": {"role": "model", "parts": [{"text": text}]}}]})
+    }
+
+    #[tokio::test]
+    async fn generates_content() {
+        let server = MockServer::start(|_| {
+            Reply::json(json!({
+                "candidates": [{
+                    "content": {"parts": [{"text": " 你好，"}, {"text": "世界 "}]},
+                    "finishReason": "STOP"
+                }]
+            }))
+        })
+        .await;
+
+        let req = mock::request("Hello, world", "en", "zh-CN");
+        let result = GeminiTranslator::new(&entry(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好，世界");
+        let sent = &server.requests()[0];
+        assert_eq!(sent.path, "/v1beta/models/test-model:generateContent");
+        assert_eq!(sent.header("x-goog-api-key"), Some("gm-test"));
+        assert_eq!(sent.json()["generationConfig"]["maxOutputTokens"], 1024);
+        assert!(sent.json()["systemInstruction"]["parts"][0]["text"].is_string());
+    }
+
+    #[tokio::test]
+    async fn streams_candidate_chunks() {
+        let body = format!(
+            "data: {}\r\n\r\ndata: {}\r\n\r\ndata: {}\r\n\r\n",
+            candidate("你好"),
+            json!({"candidates": [{"content": {"parts": []}}]}),
+            json!({"candidates": [{"content": {"parts": [{"text": "，世界"}]}, "finishReason": "STOP"}]}),
+        );
+        let server = MockServer::start(move |_| {
+            let body = body.as_bytes();
+            Reply::sse(&[&body[..40], &body[40..]])
+        })
+        .await;
+
+        let (req, deltas) = mock::streaming_request("Hello, world", "en", "zh-CN");
+        let result = GeminiTranslator::new(&entry(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好，世界");
+        assert_eq!(*deltas.lock().unwrap(), ["你好", "，世界"]);
+        assert_eq!(
+            server.requests()[0].path,
+            "/v1beta/models/test-model:streamGenerateContent?alt=sse"
+        );
+    }
+
+    #[tokio::test]
+    async fn blocked_response_is_a_provider_error() {
+        let server = MockServer::start(|_| {
+            Reply::json(json!({"candidates": [{"content": {"parts": []}, "finishReason": "SAFETY"}]}))
+        })
+        .await;
+
+        let req = mock::request("Hello", "en", "zh-CN");
+        let err = GeminiTranslator::new(&entry(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, TranslateError::Provider { ref code, .. } if code == "SAFETY"));
+    }
+
+    #[tokio::test]
+    async fn invalid_key_is_an_auth_error() {
+        let server = MockServer::start(|_| {
+            Reply::status(
+                400,
+                json!({"error": {
+                    "code": 400,
+                    "message": "API key not valid. Please pass a valid API key.",
+                    "status": "INVALID_ARGUMENT"
+                }}),
+            )
+        })
+        .await;
+
+        let req = mock::request("Hello", "en", "zh-CN");
+        let err = GeminiTranslator::new(&entry(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, TranslateError::Auth { .. }));
+    }
+
+    #[tokio::test]
+    async fn error_event_in_stream() {
+        let server = MockServer::start(|_| {
+            Reply::sse(&[b"data: {\"error\":{\"code\":503,\"message\":\"busy\",\"status\":\"UNAVAILABLE\"}}\n\n"])
+        })
+        .await;
+
+        let (req, _) = mock::streaming_request("Hello", "en", "zh-CN");
+        let err = GeminiTranslator::new(&entry(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, TranslateError::Provider { ref code, .. } if code == "UNAVAILABLE"));
+    }
+
+    #[test]
+    fn candidate_text_reports_blocks() {
+        assert_eq!(candidate_text(&candidate("ok")).unwrap(), "ok");
+        // A safety stop that still produced text keeps the text
+        let partial = json!({"candidates": [{"content": {"parts": [{"text": "part"}]}, "finishReason": "SAFETY"}]});
+        assert_eq!(candidate_text(&partial).unwrap(), "part");
+        let blocked = json!({"promptFeedback": {"blockReason": "OTHER"}});
+        assert!(matches!(
+            candidate_text(&blocked),
+            Err(TranslateError::Provider { ref code, .. }) if code == "OTHER"
+        ));
+        assert_eq!(candidate_text(&json!({})).unwrap(), "");
+    }
+}
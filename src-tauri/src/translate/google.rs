@@ -0,0 +1,461 @@
+use super::chunk::paragraphs;
+use super::langmap;
+use super::{base_url_or, check_status, Capabilities, TranslateError, TranslateRequest, TranslateResult, Translator};
+use crate::config::GoogleServiceEntry;
+use crate::dictionary::{self, DictionaryEntry, Meaning};
+use async_trait::async_trait;
+use reqwest::{Client, Response};
+use serde_json::Value;
+
+// ==================== Google Translate ====================
+
+const FREE_URL: &str = "https://translate.googleapis.com";
+const CLOUD_URL: &str = "https://translation.googleapis.com";
+
+/// Most `q` values (v2) or `contents` (v3) in one Cloud Translation request
+const MAX_SEGMENTS: usize = 128;
+
+/// The free web endpoint, or Cloud Translation once an API key is set
+pub struct GoogleTranslator {
+    api_key: String,
+    project_id: String,
+    location: String,
+    glossary_id: String,
+    /// `FREE_URL` or `CLOUD_URL` unless overridden
+    base_url: String,
+}
+
+impl GoogleTranslator {
+    pub fn new(entry: &GoogleServiceEntry) -> Self {
+        let api_key = entry.api_key.trim().to_string();
+        let default_url = if api_key.is_empty() { FREE_URL } else { CLOUD_URL };
+        Self {
+            base_url: base_url_or(&entry.base_url, default_url),
+            api_key,
+            project_id: entry.project_id.trim().to_string(),
+            location: entry.location.trim().to_string(),
+            glossary_id: entry.glossary_id.trim().to_string(),
+        }
+    }
+
+    fn is_cloud(&self) -> bool {
+        !self.api_key.is_empty()
+    }
+}
+
+/// Build a dictionary entry from the `dt=bd` (translations by part of speech),
+/// `dt=md` (definitions), `dt=rm` (transliteration) and `dt=ex` (examples) blocks
+fn parse_dictionary(json: &Value, word: &str) -> Option<DictionaryEntry> {
+    let mut entry = DictionaryEntry {
+        word: word.to_string(),
+        source: "Google".to_string(),
+        ..Default::default()
+    };
+
+    // The transliteration row is the sentence entry without a translation
+    if let Some(sentences) = json[0].as_array() {
+        for row in sentences.iter().filter(|r| r[0].is_null()) {
+            if let Some(translit) = row[3].as_str().filter(|t| !t.is_empty()) {
+                entry.phonetics.push(translit.to_string());
+            }
+        }
+    }
+
+    // [pos, [terms], [[term, [back translations], ...]], base_form, ...]
+    if let Some(groups) = json[1].as_array() {
+        for group in groups {
+            let definitions: Vec<String> = group[1]
+                .as_array()
+                .map(|terms| {
+                    terms
+                        .iter()
+                        .filter_map(|t| t.as_str().map(|t| t.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            if !definitions.is_empty() {
+                entry.meanings.push(Meaning {
+                    part_of_speech: group[0].as_str().unwrap_or("").to_string(),
+                    definitions,
+                });
+            }
+        }
+    }
+
+    // [[pos, [[definition, id, example], ...], base_form], ...]
+    // Source-language definitions are only used when there are no translated terms
+    let use_definitions = entry.meanings.is_empty();
+    if let Some(groups) = json[12].as_array() {
+        for group in groups {
+            let Some(defs) = group[1].as_array() else {
+                continue;
+            };
+            entry.examples.extend(
+                defs.iter()
+                    .filter_map(|d| d[2].as_str().map(|s| s.to_string())),
+            );
+            if use_definitions {
+                entry.meanings.push(Meaning {
+                    part_of_speech: group[0].as_str().unwrap_or("").to_string(),
+                    definitions: defs
+                        .iter()
+                        .filter_map(|d| d[0].as_str().map(|s| s.to_string()))
+                        .collect(),
+                });
+            }
+        }
+    }
+
+    // [[[example_html, null, null, null, null, id], ...]]
+    if let Some(examples) = json[13][0].as_array() {
+        entry.examples.extend(
+            examples
+                .iter()
+                .filter_map(|e| e[0].as_str().map(dictionary::strip_markup)),
+        );
+    }
+    entry.examples.dedup();
+
+    (!entry.meanings.is_empty()).then_some(entry)
+}
+
+// ==================== Cloud Translation ====================
+
+/// Map a Cloud Translation error body to an error kind, going by
+/// `error.status` and the `reason` in `error.errors` (v2) or `error.details` (v3)
+async fn cloud_error(resp: Response) -> TranslateError {
+    let status = resp.status();
+    let body = resp.text().await.unwrap_or_default();
+    let json: Value = serde_json::from_str(&body).unwrap_or_default();
+    let error = &json["error"];
+    let message = error["message"]
+        .as_str()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| body.chars().take(200).collect());
+    let reasons: Vec<&str> = error["errors"]
+        .as_array()
+        .into_iter()
+        .chain(error["details"].as_array())
+        .flatten()
+        .filter_map(|e| e["reason"].as_str())
+        .collect();
+    let has = |names: &[&str]| reasons.iter().any(|r| names.contains(r));
+
+    if has(&["keyInvalid", "API_KEY_INVALID", "accessNotConfigured", "SERVICE_DISABLED"])
+        || error["status"] == "UNAUTHENTICATED"
+    {
+        TranslateError::Auth { message }
+    } else if has(&["dailyLimitExceeded", "quotaExceeded", "billingNotEnabled", "BILLING_DISABLED"]) {
+        TranslateError::Quota { message }
+    } else if has(&["rateLimitExceeded", "userRateLimitExceeded", "RATE_LIMIT_EXCEEDED"]) {
+        TranslateError::RateLimit { retry_after: None }
+    } else if error["status"] == "RESOURCE_EXHAUSTED" {
+        // Per-minute limits clear by themselves, daily ones don't
+        if message.contains("per day") {
+            TranslateError::Quota { message }
+        } else {
+            TranslateError::RateLimit { retry_after: None }
+        }
+    } else {
+        TranslateError::from_status(status, message, None)
+    }
+}
+
+/// Each item's `translatedText`, and the language detected for the first one
+fn read_translations(list: &Value, detected_key: &str) -> (Vec<String>, Option<String>) {
+    let items = list.as_array().map(Vec::as_slice).unwrap_or_default();
+    let translations = items
+        .iter()
+        .map(|t| t["translatedText"].as_str().unwrap_or("").to_string())
+        .collect();
+    let detected = items
+        .first()
+        .and_then(|t| t[detected_key].as_str())
+        .map(|code| code.to_string());
+    (translations, detected)
+}
+
+impl GoogleTranslator {
+    fn glossary_name(&self) -> String {
+        if self.glossary_id.contains('/') {
+            self.glossary_id.clone()
+        } else {
+            format!(
+                "projects/{}/locations/{}/glossaries/{}",
+                self.project_id, self.location, self.glossary_id
+            )
+        }
+    }
+
+    /// v2 `translate`: one translation per `q`, plus the detected language
+    async fn translate_v2(
+        &self,
+        client: &Client,
+        contents: &[&str],
+        source: Option<&str>,
+        target: &str,
+    ) -> Result<(Vec<String>, Option<String>), TranslateError> {
+        let mut body = serde_json::json!({
+            "q": contents,
+            "target": target,
+            "format": "text"
+        });
+        if let Some(source) = source {
+            body["source"] = serde_json::json!(source);
+        }
+        let resp = client
+            .post(format!("{}/language/translate/v2", self.base_url))
+            .query(&[("key", &self.api_key)])
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            return Err(cloud_error(resp).await);
+        }
+        let json = resp.json::<Value>().await?;
+        Ok(read_translations(&json["data"]["translations"], "detectedSourceLanguage"))
+    }
+
+    /// v3 `translateText`, with the configured glossary when the source is known
+    async fn translate_v3(
+        &self,
+        client: &Client,
+        contents: &[&str],
+        source: Option<&str>,
+        target: &str,
+    ) -> Result<(Vec<String>, Option<String>), TranslateError> {
+        let mut body = serde_json::json!({
+            "contents": contents,
+            "targetLanguageCode": target,
+            "mimeType": "text/plain"
+        });
+        if let Some(source) = source {
+            body["sourceLanguageCode"] = serde_json::json!(source);
+            // Glossaries require an explicit source language
+            if !self.glossary_id.is_empty() {
+                body["glossaryConfig"] = serde_json::json!({ "glossary": self.glossary_name() });
+            }
+        }
+        let url = format!(
+            "{}/v3/projects/{}/locations/{}:translateText",
+            self.base_url, self.project_id, self.location
+        );
+        let request = client.post(url).json(&body);
+        // OAuth access tokens (`gcloud auth print-access-token`) start with "ya29."
+        let request = if self.api_key.starts_with("ya29.") {
+            request.bearer_auth(&self.api_key)
+        } else {
+            request.header("x-goog-api-key", &self.api_key)
+        };
+        let resp = request.send().await?;
+        if !resp.status().is_success() {
+            return Err(cloud_error(resp).await);
+        }
+        let json = resp.json::<Value>().await?;
+        // With a glossary both lists come back; the glossary one is what was asked for
+        let translations = match json.get("glossaryTranslations") {
+            Some(list) if list.is_array() => list,
+            _ => &json["translations"],
+        };
+        Ok(read_translations(translations, "detectedLanguageCode"))
+    }
+
+    async fn translate_cloud(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        let (source, target) = langmap::map_pair(self.id(), req)?;
+
+        // Each paragraph goes as its own segment so line breaks survive exactly
+        let mut segments = paragraphs(&req.text);
+        if segments.len() > MAX_SEGMENTS {
+            segments = vec![(req.text.as_str(), "")];
+        }
+        let contents: Vec<&str> = segments
+            .iter()
+            .map(|(text, _)| *text)
+            .filter(|text| !text.trim().is_empty())
+            .collect();
+        if contents.is_empty() {
+            return Ok(TranslateResult::ok(self.name(), req.text.clone(), req.source.clone(), &req.target));
+        }
+
+        let (translations, detected) = if self.project_id.is_empty() {
+            self.translate_v2(client, &contents, source, target).await?
+        } else {
+            self.translate_v3(client, &contents, source, target).await?
+        };
+        if translations.len() != contents.len() {
+            return Err(TranslateError::Parse {
+                message: format!("expected {} translations, got {}", contents.len(), translations.len()),
+            });
+        }
+
+        let mut translated = String::new();
+        let mut translations = translations.into_iter();
+        for (text, sep) in segments {
+            if text.trim().is_empty() {
+                translated.push_str(text);
+            } else {
+                translated.push_str(&translations.next().unwrap_or_default());
+            }
+            translated.push_str(sep);
+        }
+        let detected = detected
+            .map(|code| langmap::from_service_code(self.id(), &code))
+            .unwrap_or_else(|| req.source.clone());
+        Ok(TranslateResult::ok(self.name(), translated, detected, &req.target))
+    }
+
+    /// The free `client=gtx` endpoint, with dictionary data for single words
+    async fn translate_free(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        let (source, tl) = langmap::map_pair(self.id(), req)?;
+        let sl = source.unwrap_or("auto");
+        let is_word = dictionary::looks_like_word(&req.text);
+        let dt = if is_word {
+            "dt=t&dt=bd&dt=md&dt=rm&dt=ex"
+        } else {
+            "dt=t"
+        };
+        let url = format!(
+            "{}/translate_a/single?client=gtx&sl={}&tl={}&{}&q={}",
+            self.base_url,
+            sl,
+            tl,
+            dt,
+            urlencoding::encode(&req.text)
+        );
+
+        let resp = check_status(client.get(&url).send().await?).await?;
+        let json = resp.json::<serde_json::Value>().await?;
+
+        let mut translated = String::new();
+        let detected = json[2]
+            .as_str()
+            .map(|code| langmap::from_service_code(self.id(), code))
+            .unwrap_or_else(|| req.source.clone());
+        let sentences = json[0].as_array().ok_or_else(|| TranslateError::Parse {
+            message: "missing sentences".to_string(),
+        })?;
+        for sentence in sentences {
+            if let Some(t) = sentence[0].as_str() {
+                translated.push_str(t);
+            }
+        }
+        let mut result = TranslateResult::ok(self.name(), translated, detected, &req.target);
+        if is_word {
+            result.dictionary = parse_dictionary(&json, req.text.trim());
+        }
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl Translator for GoogleTranslator {
+    fn id(&self) -> &'static str {
+        "google"
+    }
+
+    fn name(&self) -> String {
+        "Google".to_string()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_credentials: false,
+            detects_language: true,
+            streaming: false,
+            // Only the free endpoint returns dictionary data
+            dictionary: !self.is_cloud(),
+            glossary: false,
+            // The free endpoint takes the text in the GET query string;
+            // Cloud Translation recommends at most 5000 chars per request
+            max_chars: if self.is_cloud() { 5000 } else { 1800 },
+            max_batch: 1,
+            prompt_modes: false,
+        }
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        if self.is_cloud() {
+            self.translate_cloud(client, req).await
+        } else {
+            self.translate_free(client, req).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock::{self, MockServer, Reply};
+    use serde_json::json;
+
+    fn entry(server: &MockServer, api_key: &str, project_id: &str) -> GoogleServiceEntry {
+        GoogleServiceEntry {
+            enabled: true,
+            api_key: api_key.to_string(),
+            project_id: project_id.to_string(),
+            location: "global".to_string(),
+            glossary_id: String::new(),
+            base_url: format!("{}/proxy/", server.url),
+        }
+    }
+
+    #[tokio::test]
+    async fn free_endpoint_honours_base_url() {
+        let server = MockServer::start(|_| Reply::json(json!([[["你好，世界", "Hello, world", null, null, 1]], null, "en"])))
+            .await;
+
+        let req = mock::request("Hello, world", "auto", "zh-CN");
+        let result = GoogleTranslator::new(&entry(&server, "", ""))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好，世界");
+        assert_eq!(result.source_lang, "en");
+        let sent = &server.requests()[0];
+        assert_eq!(sent.method, "GET");
+        assert!(sent.path.starts_with("/proxy/translate_a/single?client=gtx&sl=auto&tl=zh-CN&dt=t&q=Hello"));
+    }
+
+    #[tokio::test]
+    async fn cloud_v2_honours_base_url() {
+        let server = MockServer::start(|_| {
+            Reply::json(json!({"data": {"translations": [
+                {"translatedText": "你好", "detectedSourceLanguage": "en"},
+                {"translatedText": "世界", "detectedSourceLanguage": "en"}
+            ]}}))
+        })
+        .await;
+
+        let req = mock::request("Hello\n\nworld", "auto", "zh-CN");
+        let result = GoogleTranslator::new(&entry(&server, "AIza-test", ""))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好\n\n世界");
+        let sent = &server.requests()[0];
+        assert_eq!(sent.path, "/proxy/language/translate/v2?key=AIza-test");
+        assert_eq!(sent.json()["q"], json!(["Hello", "world"]));
+    }
+
+    #[tokio::test]
+    async fn cloud_v3_honours_base_url() {
+        let server = MockServer::start(|_| {
+            Reply::json(json!({"translations": [{"translatedText": "你好", "detectedLanguageCode": "en"}]}))
+        })
+        .await;
+
+        let req = mock::request("Hello", "en", "zh-CN");
+        let result = GoogleTranslator::new(&entry(&server, "AIza-test", "my-project"))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好");
+        let sent = &server.requests()[0];
+        assert_eq!(sent.path, "/proxy/v3/projects/my-project/locations/global:translateText");
+        assert_eq!(sent.header("x-goog-api-key"), Some("AIza-test"));
+        assert_eq!(sent.json()["sourceLanguageCode"], "en");
+    }
+}
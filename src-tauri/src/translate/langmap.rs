@@ -0,0 +1,170 @@
+use super::{TranslateError, TranslateRequest};
+use serde::Serialize;
+use std::sync::OnceLock;
+
+// ==================== Language Catalog ====================
+
+/// Tab-separated catalog: info columns, then one code column per service
+const CATALOG: &str = include_str!("languages.tsv");
+
+/// Columns before the per-service codes
+const INFO_COLUMNS: usize = 6;
+
+/// LLMs are prompted with the language name and handle every language
+const LLM_SERVICES: &[&str] = &["openai", "anthropic", "gemini"];
+
+/// One catalog entry as exposed to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct Language {
+    pub code: &'static str,
+    /// Display name in the UI language
+    pub name: &'static str,
+    pub name_en: &'static str,
+    pub native: &'static str,
+    /// ISO 15924 script code
+    pub script: &'static str,
+    pub rtl: bool,
+    /// Ids of the services that accept this language
+    pub services: Vec<&'static str>,
+    /// Each catalog service's code, `None` if unsupported
+    #[serde(skip)]
+    codes: Vec<Option<&'static str>>,
+}
+
+impl Language {
+    /// The "auto" pseudo-language offered as a source
+    pub fn auto() -> Self {
+        Self {
+            code: "auto",
+            name: "自动检测",
+            name_en: "Auto Detect",
+            native: "",
+            script: "",
+            rtl: false,
+            services: Vec::new(),
+            codes: Vec::new(),
+        }
+    }
+}
+
+struct Catalog {
+    /// Service ids of the code columns, in column order
+    services: Vec<&'static str>,
+    languages: Vec<Language>,
+}
+
+fn parse_row(services: &[&'static str], line: &'static str) -> Option<Language> {
+    let fields: Vec<&'static str> = line.split('\t').collect();
+    if fields.len() != INFO_COLUMNS + services.len() {
+        return None;
+    }
+    let codes: Vec<Option<&'static str>> = fields[INFO_COLUMNS..]
+        .iter()
+        .map(|&c| (c != "-" && !c.is_empty()).then_some(c))
+        .collect();
+    let supported = services
+        .iter()
+        .zip(&codes)
+        .filter(|(_, code)| code.is_some())
+        .map(|(&id, _)| id);
+    Some(Language {
+        code: fields[0],
+        name: fields[1],
+        name_en: fields[2],
+        native: fields[3],
+        script: fields[4],
+        rtl: fields[5] == "1",
+        services: supported.chain(LLM_SERVICES.iter().copied()).collect(),
+        codes,
+    })
+}
+
+fn catalog() -> &'static Catalog {
+    static CACHE: OnceLock<Catalog> = OnceLock::new();
+    CACHE.get_or_init(|| {
+        let mut lines = CATALOG
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.starts_with('#'));
+        let services: Vec<&'static str> = lines
+            .next()
+            .map(|header| header.split('\t').skip(INFO_COLUMNS).collect())
+            .unwrap_or_default();
+        let languages = lines
+            .filter_map(|line| {
+                let row = parse_row(&services, line);
+                if row.is_none() {
+                    eprintln!("[Languages] Skipping malformed catalog row: {}", line);
+                }
+                row
+            })
+            .collect();
+        Catalog { services, languages }
+    })
+}
+
+/// Every language in the catalog, in menu order
+pub fn languages() -> &'static [Language] {
+    &catalog().languages
+}
+
+fn column(lang: &Language, service: &str) -> Option<&'static str> {
+    if LLM_SERVICES.contains(&service) {
+        return Some(lang.name_en);
+    }
+    let index = catalog().services.iter().position(|&s| s == service)?;
+    lang.codes[index]
+}
+
+/// The service's code for one of our codes, `None` if unsupported
+pub fn service_code(service: &str, code: &str) -> Option<&'static str> {
+    languages()
+        .iter()
+        .find(|r| r.code == code)
+        .and_then(|r| column(r, service))
+}
+
+/// Our codes the service supports, in catalog order
+pub fn codes_for(service: &str) -> Vec<&'static str> {
+    languages()
+        .iter()
+        .filter(|r| column(r, service).is_some())
+        .map(|r| r.code)
+        .collect()
+}
+
+/// The request's source (`None` for "auto") and target in the service's codes,
+/// or `UnsupportedLanguage` for whichever side it cannot handle
+pub fn map_pair(
+    service: &str,
+    req: &TranslateRequest,
+) -> Result<(Option<&'static str>, &'static str), TranslateError> {
+    let unsupported = |lang: &str| TranslateError::UnsupportedLanguage {
+        lang: lang.to_string(),
+    };
+    let source = if req.source == "auto" {
+        None
+    } else {
+        Some(service_code(service, &req.source).ok_or_else(|| unsupported(&req.source))?)
+    };
+    let target = service_code(service, &req.target).ok_or_else(|| unsupported(&req.target))?;
+    Ok((source, target))
+}
+
+/// Our code for a language code the service reported (e.g. its detected
+/// source), matching case-insensitively and then by base code ("EN" -> "en").
+/// Unknown codes come back lowercased.
+pub fn from_service_code(service: &str, code: &str) -> String {
+    let base = |c: &str| c.split('-').next().unwrap_or(c).to_ascii_lowercase();
+    let exact = languages()
+        .iter()
+        .find(|r| column(r, service).is_some_and(|c| c.eq_ignore_ascii_case(code)));
+    let by_base = || {
+        languages()
+            .iter()
+            .find(|r| column(r, service).is_some_and(|c| base(c) == base(code)))
+    };
+    exact
+        .or_else(by_base)
+        .map(|r| r.code.to_string())
+        .unwrap_or_else(|| code.to_lowercase())
+}
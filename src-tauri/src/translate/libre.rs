@@ -0,0 +1,210 @@
+use super::{check_status, langmap, Capabilities, TranslateError, TranslateRequest, TranslateResult, Translator};
+use crate::config::LibreServiceEntry;
+use async_trait::async_trait;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+// ==================== LibreTranslate ====================
+
+/// Codes each server listed at `/languages`, by base URL. Until a server has
+/// answered, the codes of the catalog's `libre` column are assumed.
+static SERVER_LANGUAGES: OnceLock<Mutex<HashMap<String, Vec<String>>>> = OnceLock::new();
+
+fn server_languages(base_url: &str) -> Option<Vec<String>> {
+    SERVER_LANGUAGES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .get(base_url)
+        .cloned()
+}
+
+/// Fetch and remember the server's language list, returning how many it has
+pub async fn load_languages(client: &Client, entry: &LibreServiceEntry) -> Result<usize, TranslateError> {
+    let base_url = entry.base_url.trim_end_matches('/');
+    let resp = client
+        .get(format!("{}/languages", base_url))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await?;
+    let json = check_status(resp).await?.json::<Vec<serde_json::Value>>().await?;
+    let codes: Vec<String> = json
+        .iter()
+        .filter_map(|lang| lang["code"].as_str())
+        .map(str::to_string)
+        .collect();
+    let count = codes.len();
+    SERVER_LANGUAGES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .insert(base_url.to_string(), codes);
+    Ok(count)
+}
+
+/// How a server spells a catalog code: as listed, or the older forms
+/// ("zh" for zh-Hans, "zt" for zh-Hant, "no" for nb)
+fn resolve(code: &str, available: &[String]) -> Option<String> {
+    let candidates: &[&str] = match code {
+        "zh-Hans" => &["zh-Hans", "zh"],
+        "zh-Hant" => &["zh-Hant", "zt"],
+        "nb" => &["nb", "no"],
+        _ => &[code],
+    };
+    candidates
+        .iter()
+        .find(|c| available.iter().any(|a| a.eq_ignore_ascii_case(c)))
+        .map(|c| c.to_string())
+}
+
+pub struct LibreTranslator {
+    base_url: String,
+    api_key: String,
+}
+
+impl LibreTranslator {
+    pub fn new(entry: &LibreServiceEntry) -> Self {
+        Self {
+            base_url: entry.base_url.trim_end_matches('/').to_string(),
+            api_key: entry.api_key.clone(),
+        }
+    }
+
+    /// The server's code for a catalog `libre` code, `None` if it lacks the language
+    fn server_code(&self, code: &str) -> Option<String> {
+        match server_languages(&self.base_url) {
+            Some(available) => resolve(code, &available),
+            None => Some(code.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+impl Translator for LibreTranslator {
+    fn id(&self) -> &'static str {
+        "libre"
+    }
+
+    fn name(&self) -> String {
+        "LibreTranslate".to_string()
+    }
+
+    fn supported_languages(&self) -> Vec<&'static str> {
+        langmap::codes_for(self.id())
+            .into_iter()
+            .filter(|code| {
+                langmap::service_code(self.id(), code)
+                    .and_then(|c| self.server_code(c))
+                    .is_some()
+            })
+            .collect()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_credentials: false,
+            detects_language: true,
+            streaming: false,
+            dictionary: false,
+            glossary: false,
+            max_chars: 2000,
+            max_batch: 1,
+            prompt_modes: false,
+        }
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        if self.base_url.is_empty() {
+            return Err(TranslateError::NotConfigured);
+        }
+
+        let unsupported = |lang: &str| TranslateError::UnsupportedLanguage {
+            lang: lang.to_string(),
+        };
+        let (source, target) = langmap::map_pair(self.id(), req)?;
+        let target = self.server_code(target).ok_or_else(|| unsupported(&req.target))?;
+        let source = match source {
+            Some(code) => self.server_code(code).ok_or_else(|| unsupported(&req.source))?,
+            None => "auto".to_string(),
+        };
+
+        let mut body = serde_json::json!({
+            "q": req.text,
+            "source": source,
+            "target": target,
+            "format": "text"
+        });
+        if !self.api_key.is_empty() {
+            body["api_key"] = serde_json::json!(self.api_key);
+        }
+
+        let resp = client
+            .post(format!("{}/translate", self.base_url))
+            .json(&body)
+            .send()
+            .await?;
+        let json = check_status(resp).await?.json::<serde_json::Value>().await?;
+
+        let translated = json["translatedText"]
+            .as_str()
+            .ok_or_else(|| TranslateError::Parse {
+                message: "missing translatedText".to_string(),
+            })?
+            .to_string();
+        let detected = match json["detectedLanguage"]["language"].as_str() {
+            Some(code) if req.source == "auto" => {
+                langmap::from_service_code(self.id(), if code == "zt" { "zh-Hant" } else { code })
+            }
+            _ => req.source.clone(),
+        };
+        Ok(TranslateResult::ok(self.name(), translated, detected, &req.target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock::{self, MockServer, Reply};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn uses_codes_the_server_lists() {
+        let server = MockServer::start(|r| {
+            if r.path.ends_with("/languages") {
+                Reply::json(json!([{"code": "en", "name": "English"}, {"code": "zh", "name": "Chinese"}]))
+            } else {
+                Reply::json(json!({"translatedText": "你好", "detectedLanguage": {"language": "en", "confidence": 90}}))
+            }
+        })
+        .await;
+        let entry = LibreServiceEntry {
+            enabled: true,
+            base_url: format!("{}/proxy/", server.url),
+            api_key: "libre-key".to_string(),
+        };
+        let client = Client::new();
+        assert_eq!(load_languages(&client, &entry).await.unwrap(), 2);
+
+        let translator = LibreTranslator::new(&entry);
+        let result = translator
+            .translate(&client, &mock::request("Hello", "auto", "zh-CN"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好");
+        assert_eq!(result.source_lang, "en");
+        let sent = &server.requests()[1];
+        assert_eq!(sent.path, "/proxy/translate");
+        // The server only knows the older "zh" code
+        assert_eq!(sent.json()["target"], "zh");
+        assert_eq!(sent.json()["api_key"], "libre-key");
+
+        let err = translator
+            .translate(&client, &mock::request("Hello", "en", "ja"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TranslateError::UnsupportedLanguage { ref lang } if lang == "ja"));
+    }
+}
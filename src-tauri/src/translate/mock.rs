@@ -0,0 +1,214 @@
+use super::{PromptMode, TranslateRequest};
+use crate::config::PromptConfig;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+// ==================== Mock HTTP Server (tests) ====================
+
+// A local HTTP/1.1 server for provider tests. Each connection carries one
+// request and is closed after the reply, so every call is recorded on its own.
+
+/// A request as the server received it; header names are lowercase
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: String,
+    /// Path with the query string
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Recorded {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_str(&self.body).unwrap_or_default()
+    }
+
+    /// A field of a `application/x-www-form-urlencoded` body
+    pub fn form(&self, name: &str) -> Option<String> {
+        self.body
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| urlencoding::decode(&v.replace('+', " ")).unwrap_or_default().into_owned())
+    }
+}
+
+pub struct Reply {
+    status: u16,
+    content_type: &'static str,
+    headers: Vec<(String, String)>,
+    /// Written and flushed one at a time, so the client sees the body split there
+    parts: Vec<Vec<u8>>,
+}
+
+impl Reply {
+    pub fn json(body: serde_json::Value) -> Self {
+        Self::status(200, body)
+    }
+
+    pub fn status(status: u16, body: serde_json::Value) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            headers: Vec::new(),
+            parts: vec![body.to_string().into_bytes()],
+        }
+    }
+
+    pub fn text(body: &str) -> Self {
+        Self {
+            status: 200,
+            content_type: "text/plain",
+            headers: Vec::new(),
+            parts: vec![body.as_bytes().to_vec()],
+        }
+    }
+
+    /// A `text/event-stream` body delivered in the given pieces
+    pub fn sse(parts: &[&[u8]]) -> Self {
+        Self {
+            status: 200,
+            content_type: "text/event-stream",
+            headers: Vec::new(),
+            parts: parts.iter().map(|p| p.to_vec()).collect(),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+
+type Handler = dyn Fn(&Recorded) -> Reply + Send + Sync;
+
+pub struct MockServer {
+    /// `http://127.0.0.1:<port>`, without a trailing slash
+    pub url: String,
+    requests: Arc<Mutex<Vec<Recorded>>>,
+}
+
+impl MockServer {
+    pub async fn start(handler: impl Fn(&Recorded) -> Reply + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+        let log = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let handler = handler.clone();
+                let log = log.clone();
+                tokio::spawn(async move { serve(stream, handler, log).await });
+            }
+        });
+        Self { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn serve(mut stream: TcpStream, handler: Arc<Handler>, log: Arc<Mutex<Vec<Recorded>>>) {
+    let Some(request) = read_request(&mut stream).await else {
+        return;
+    };
+    let reply = handler(&request);
+    log.lock().unwrap().push(request);
+
+    let mut head = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: {}\r\nConnection: close\r\n",
+        reply.status, reply.content_type
+    );
+    for (name, value) in &reply.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    // Streamed bodies end when the connection closes
+    if reply.parts.len() == 1 {
+        head.push_str(&format!("Content-Length: {}\r\n", reply.parts[0].len()));
+    }
+    head.push_str("\r\n");
+    stream.write_all(head.as_bytes()).await.ok();
+    for part in &reply.parts {
+        stream.write_all(part).await.ok();
+        stream.flush().await.ok();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    stream.shutdown().await.ok();
+}
+
+async fn read_request(stream: &mut TcpStream) -> Option<Recorded> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        let n = stream.read(&mut chunk).await.ok().filter(|&n| n > 0)?;
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut start = lines.next()?.split(' ');
+    let method = start.next()?.to_string();
+    let path = start.next()?.to_string();
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(n, v)| (n.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+
+    let length = headers
+        .iter()
+        .find(|(n, _)| n == "content-length")
+        .and_then(|(_, v)| v.parse::<usize>().ok())
+        .unwrap_or(0);
+    let mut body = buf[head_end + 4..].to_vec();
+    while body.len() < length {
+        let n = stream.read(&mut chunk).await.ok().filter(|&n| n > 0)?;
+        body.extend_from_slice(&chunk[..n]);
+    }
+
+    Some(Recorded {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+/// A plain translation request with the default prompt template
+pub fn request(text: &str, source: &str, target: &str) -> TranslateRequest {
+    TranslateRequest {
+        text: text.to_string(),
+        source: source.to_string(),
+        detected: source.to_string(),
+        target: target.to_string(),
+        on_delta: None,
+        glossary: Vec::new(),
+        mode: PromptMode::Translate,
+        prompt: super::resolve_template(PromptMode::Translate, &PromptConfig::default()),
+        context: String::new(),
+    }
+}
+
+/// A request whose streamed deltas are collected into the returned list
+pub fn streaming_request(text: &str, source: &str, target: &str) -> (TranslateRequest, Arc<Mutex<Vec<String>>>) {
+    let deltas = Arc::new(Mutex::new(Vec::new()));
+    let sink = deltas.clone();
+    let mut req = request(text, source, target);
+    req.on_delta = Some(Arc::new(move |d: super::StreamDelta| {
+        sink.lock().unwrap().push(d.delta);
+    }));
+    (req, deltas)
+}
@@ -0,0 +1,369 @@
+mod anthropic;
+mod baidu;
+mod bing;
+mod caiyun;
+mod chunk;
+mod deepl;
+mod detect;
+mod error;
+mod gemini;
+mod google;
+mod langmap;
+mod libre;
+#[cfg(test)]
+mod mock;
+mod openai;
+mod policy;
+mod prompt;
+mod sign;
+mod sse;
+mod tencent;
+mod volcano;
+mod youdao;
+
+pub use anthropic::AnthropicTranslator;
+pub use baidu::BaiduTranslator;
+pub use bing::BingTranslator;
+pub use caiyun::CaiyunTranslator;
+pub use deepl::{load_usage as load_deepl_usage, DeepLTranslator};
+pub use detect::{detect, Detection};
+pub use error::{check_status, TranslateError};
+pub use gemini::GeminiTranslator;
+pub use google::GoogleTranslator;
+pub use langmap::Language;
+pub use libre::{load_languages as load_libre_languages, LibreTranslator};
+pub use openai::OpenAITranslator;
+pub use prompt::{prompt_presets, resolve_template, PromptMode, PromptPreset};
+pub use tencent::TencentTranslator;
+pub use volcano::VolcanoTranslator;
+pub use youdao::YoudaoTranslator;
+use chunk::Chunked;
+use policy::Guarded;
+
+use crate::config::{PromptTemplate, ServicesConfig, TargetRule};
+use crate::dictionary::DictionaryEntry;
+use crate::glossary::GlossaryEntry;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslateResult {
+    pub service: String,
+    pub translated: String,
+    pub source_lang: String,
+    pub target_lang: String,
+    #[serde(default, deserialize_with = "error::deserialize_error")]
+    pub error: Option<TranslateError>,
+    /// Structured word entry (phonetics, parts of speech, examples) when available
+    #[serde(default)]
+    pub dictionary: Option<DictionaryEntry>,
+}
+
+impl TranslateResult {
+    pub fn ok(service: impl Into<String>, translated: String, source_lang: String, target_lang: &str) -> Self {
+        Self {
+            service: service.into(),
+            translated,
+            source_lang,
+            target_lang: target_lang.to_string(),
+            error: None,
+            dictionary: None,
+        }
+    }
+
+    /// Failed result: keeps the requested languages so the UI can still label the card
+    pub fn err(service: impl Into<String>, req: &TranslateRequest, error: TranslateError) -> Self {
+        Self {
+            service: service.into(),
+            translated: String::new(),
+            source_lang: req.source.clone(),
+            target_lang: req.target.clone(),
+            error: Some(error),
+            dictionary: None,
+        }
+    }
+}
+
+/// Receives partial output from streaming translators as it arrives
+pub type DeltaSink = Arc<dyn Fn(StreamDelta) + Send + Sync>;
+
+/// One translation job as handed to every enabled translator
+#[derive(Clone)]
+pub struct TranslateRequest {
+    pub text: String,
+    /// Source language code, may be "auto"
+    pub source: String,
+    /// Locally detected source language (same as `source` unless that is "auto")
+    pub detected: String,
+    /// Resolved target language code (never "auto")
+    pub target: String,
+    /// When set, streaming-capable translators push partial output here
+    pub on_delta: Option<DeltaSink>,
+    /// Glossary entries whose term occurs in `text`, longest first
+    pub glossary: Vec<GlossaryEntry>,
+    /// What LLM services should do with the text
+    pub mode: PromptMode,
+    /// Template for `mode`, already resolved against the user's overrides
+    pub prompt: PromptTemplate,
+    /// Surrounding text for the `{context}` prompt variable, usually empty
+    pub context: String,
+}
+
+/// A piece of output from a streaming translator, in arrival order
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamDelta {
+    pub service: String,
+    pub delta: String,
+}
+
+/// Static description of what a translator can do
+#[derive(Debug, Clone, Serialize)]
+pub struct Capabilities {
+    /// Needs an API key / app id before it can be used
+    pub requires_credentials: bool,
+    /// Reports the detected source language in its response
+    pub detects_language: bool,
+    /// Sends incremental output through `TranslateRequest::on_delta`
+    pub streaming: bool,
+    /// Fills `TranslateResult::dictionary` for single words
+    pub dictionary: bool,
+    /// Applies `TranslateRequest::glossary` itself instead of via placeholders
+    pub glossary: bool,
+    /// Longest text sent in one request; longer input is split into chunks (0 = no limit)
+    pub max_chars: usize,
+    /// Chunks sent together through `Translator::translate_batch` (1 = one per request)
+    pub max_batch: usize,
+    /// Follows `TranslateRequest::mode`; other services only take plain translations
+    pub prompt_modes: bool,
+}
+
+#[async_trait]
+pub trait Translator: Send + Sync {
+    /// Stable provider id, matches the field name in `ServicesConfig`
+    fn id(&self) -> &'static str;
+
+    /// Tells apart instances of a provider configured more than once (LLM
+    /// profiles); keys their rate limiters and request policies
+    fn instance_key(&self) -> String {
+        self.id().to_string()
+    }
+
+    /// Name shown on the result card
+    fn name(&self) -> String;
+
+    /// Language codes (from `supported_languages()`) this service accepts
+    fn supported_languages(&self) -> Vec<&'static str> {
+        langmap::codes_for(self.id())
+    }
+
+    fn capabilities(&self) -> Capabilities;
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError>;
+
+    /// Translate several texts with `req`'s languages and options, one result
+    /// per text. Services with a `max_batch` above 1 do it in one request.
+    async fn translate_batch(
+        &self,
+        client: &Client,
+        req: &TranslateRequest,
+        texts: &[String],
+    ) -> Result<Vec<TranslateResult>, TranslateError> {
+        let mut results = Vec::with_capacity(texts.len());
+        for text in texts {
+            let single = TranslateRequest {
+                text: text.clone(),
+                ..req.clone()
+            };
+            results.push(self.translate(client, &single).await?);
+        }
+        Ok(results)
+    }
+}
+
+/// Service info exposed to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceInfo {
+    pub id: String,
+    /// Same as `id` except for providers configured more than once
+    pub key: String,
+    pub name: String,
+    pub enabled: bool,
+    pub languages: Vec<&'static str>,
+    pub capabilities: Capabilities,
+}
+
+// ==================== Service Registry ====================
+
+/// A configured `base_url` without its trailing slash, else the official endpoint
+fn base_url_or(base_url: &str, default: &str) -> String {
+    match base_url.trim().trim_end_matches('/') {
+        "" => default.to_string(),
+        url => url.to_string(),
+    }
+}
+
+/// Every known translator paired with its enabled flag, in display order.
+/// Adding a provider means adding its module and one line here.
+fn all_translators(services: &ServicesConfig) -> Vec<(bool, Box<dyn Translator>)> {
+    let mut translators: Vec<(bool, Box<dyn Translator>)> = vec![
+        (services.google.enabled, Box::new(GoogleTranslator::new(&services.google))),
+        (services.bing.enabled, Box::new(BingTranslator::new(&services.bing))),
+        (services.deepl.enabled, Box::new(DeepLTranslator::new(&services.deepl))),
+        (services.baidu.enabled, Box::new(BaiduTranslator::new(&services.baidu))),
+        (services.libre.enabled, Box::new(LibreTranslator::new(&services.libre))),
+        (services.youdao.enabled, Box::new(YoudaoTranslator::new(&services.youdao))),
+        (services.tencent.enabled, Box::new(TencentTranslator::new(&services.tencent))),
+        (services.volcano.enabled, Box::new(VolcanoTranslator::new(&services.volcano))),
+        (services.caiyun.enabled, Box::new(CaiyunTranslator::new(&services.caiyun))),
+        (services.anthropic.enabled, Box::new(AnthropicTranslator::new(&services.anthropic))),
+        (services.gemini.enabled, Box::new(GeminiTranslator::new(&services.gemini))),
+    ];
+    // Each LLM profile is a translator of its own
+    for profile in &services.llm_profiles {
+        translators.push((profile.enabled, Box::new(OpenAITranslator::new(profile))));
+    }
+    translators
+}
+
+/// Build the translators enabled in the current config, each wrapped with
+/// long-text chunking and its service's request policy (applied per chunk)
+pub fn build_registry(services: &ServicesConfig) -> Vec<Box<dyn Translator>> {
+    all_translators(services)
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .map(|(_, t)| {
+            let policy = services.policy(&t.instance_key(), t.id());
+            let guarded = Box::new(Guarded::new(t, policy));
+            Box::new(Chunked::new(guarded)) as Box<dyn Translator>
+        })
+        .collect()
+}
+
+/// Languages at least one enabled service accepts
+pub fn enabled_languages(services: &ServicesConfig) -> HashSet<&'static str> {
+    all_translators(services)
+        .into_iter()
+        .filter(|(enabled, _)| *enabled)
+        .flat_map(|(_, t)| t.supported_languages())
+        .collect()
+}
+
+pub fn service_infos(services: &ServicesConfig) -> Vec<ServiceInfo> {
+    all_translators(services)
+        .into_iter()
+        .map(|(enabled, t)| ServiceInfo {
+            id: t.id().to_string(),
+            key: t.instance_key(),
+            name: t.name(),
+            enabled,
+            languages: t.supported_languages(),
+            capabilities: t.capabilities(),
+        })
+        .collect()
+}
+
+// ==================== Languages ====================
+
+/// "auto" followed by the whole catalog
+pub fn supported_languages() -> Vec<Language> {
+    std::iter::once(Language::auto())
+        .chain(langmap::languages().iter().cloned())
+        .collect()
+}
+
+/// Whether `pattern` ("*", a code, or a base code like "zh") covers `lang`
+fn lang_matches(pattern: &str, lang: &str) -> bool {
+    pattern == "*" || pattern == lang || lang.split('-').next() == Some(pattern)
+}
+
+/// Resolve an "auto" target: the first rule matching the detected language,
+/// otherwise `second` for text already in `first` and `first` for the rest.
+/// Rules that would translate a text into its own language, or into one no
+/// service in `available` accepts, are skipped.
+pub fn route_target(
+    detected: &str,
+    rules: &[TargetRule],
+    first: &str,
+    second: &str,
+    available: &HashSet<&str>,
+) -> String {
+    let rule = rules.iter().find(|r| {
+        r.target != detected
+            && available.contains(r.target.as_str())
+            && lang_matches(&r.source, detected)
+    });
+    if let Some(rule) = rule {
+        return rule.target.clone();
+    }
+    // zh-TW text counts as already being in a zh-CN first language
+    if detected.split('-').next() == first.split('-').next() {
+        second.to_string()
+    } else {
+        first.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(source: &str, target: &str) -> TargetRule {
+        TargetRule {
+            source: source.to_string(),
+            target: target.to_string(),
+        }
+    }
+
+    fn route(detected: &str, rules: &[TargetRule], available: &[&str]) -> String {
+        let available: HashSet<&str> = available.iter().copied().collect();
+        route_target(detected, rules, "zh-CN", "en", &available)
+    }
+
+    const ALL: &[&str] = &["zh-CN", "zh-TW", "en", "ja", "ko", "fr", "de"];
+
+    #[test]
+    fn patterns_match_code_base_and_any() {
+        assert!(lang_matches("ja", "ja"));
+        assert!(lang_matches("zh", "zh-TW"));
+        assert!(lang_matches("*", "ko"));
+        assert!(!lang_matches("zh-CN", "zh-TW"));
+        assert!(!lang_matches("zh-TW", "zh"));
+        assert!(!lang_matches("ja", "ko"));
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let rules = [rule("ja", "ko"), rule("*", "fr"), rule("ja", "de")];
+        assert_eq!(route("ja", &rules, ALL), "ko");
+        assert_eq!(route("en", &rules, ALL), "fr");
+
+        let rules = [rule("zh", "ja"), rule("en", "de")];
+        assert_eq!(route("zh-TW", &rules, ALL), "ja");
+        assert_eq!(route("zh-CN", &rules, ALL), "ja");
+        assert_eq!(route("en", &rules, ALL), "de");
+    }
+
+    #[test]
+    fn skips_rules_that_cannot_apply() {
+        // Target equals the source
+        let rules = [rule("*", "en"), rule("*", "de")];
+        assert_eq!(route("en", &rules, ALL), "de");
+        // No enabled service offers the target
+        let rules = [rule("ja", "ko"), rule("ja", "fr")];
+        assert_eq!(route("ja", &rules, &["zh-CN", "en", "ja", "fr"]), "fr");
+        assert_eq!(route("ja", &rules, &["zh-CN", "en", "ja"]), "zh-CN");
+    }
+
+    #[test]
+    fn falls_back_to_first_then_second_language() {
+        assert_eq!(route("ja", &[], ALL), "zh-CN");
+        assert_eq!(route("en", &[], ALL), "zh-CN");
+        assert_eq!(route("zh-CN", &[], ALL), "en");
+        assert_eq!(route("zh-TW", &[], ALL), "en");
+        let rules = [rule("fr", "de")];
+        assert_eq!(route("ja", &rules, ALL), "zh-CN");
+    }
+}
@@ -0,0 +1,239 @@
+use super::{langmap, prompt};
+use super::sse::SseParser;
+use super::{
+    check_status, Capabilities, DeltaSink, PromptMode, StreamDelta, TranslateError, TranslateRequest, TranslateResult,
+    Translator,
+};
+use crate::config::{LlmProfile, PromptTemplate};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reqwest::{Client, Response};
+
+// ==================== OpenAI / Custom LLM Translate ====================
+
+/// One configured LLM profile
+pub struct OpenAITranslator {
+    profile: LlmProfile,
+}
+
+impl OpenAITranslator {
+    pub fn new(profile: &LlmProfile) -> Self {
+        Self {
+            profile: profile.clone(),
+        }
+    }
+
+    /// Read a `stream: true` chat completion, forwarding each content delta
+    async fn read_stream(
+        &self,
+        resp: Response,
+        sink: &DeltaSink,
+    ) -> Result<String, TranslateError> {
+        let mut body = resp.bytes_stream();
+        let mut parser = SseParser::default();
+        let mut translated = String::new();
+
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk?;
+            for data in parser.feed(&chunk) {
+                if data == "[DONE]" {
+                    return Ok(translated);
+                }
+                let json: serde_json::Value = serde_json::from_str(&data)?;
+                if let Some(msg) = json["error"]["message"].as_str() {
+                    return Err(TranslateError::Provider {
+                        code: json["error"]["code"].as_str().unwrap_or("").to_string(),
+                        message: msg.to_string(),
+                    });
+                }
+                if let Some(delta) = json["choices"][0]["delta"]["content"].as_str() {
+                    if delta.is_empty() {
+                        continue;
+                    }
+                    translated.push_str(delta);
+                    sink(StreamDelta {
+                        service: self.name(),
+                        delta: delta.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(translated)
+    }
+}
+
+#[async_trait]
+impl Translator for OpenAITranslator {
+    fn id(&self) -> &'static str {
+        "openai"
+    }
+
+    fn instance_key(&self) -> String {
+        format!("openai:{}", self.profile.id)
+    }
+
+    fn name(&self) -> String {
+        format!("AI ({})", self.profile.name)
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            // Local servers such as Ollama take no key
+            requires_credentials: false,
+            detects_language: false,
+            streaming: true,
+            dictionary: false,
+            glossary: true,
+            // Keeps the answer well inside `max_tokens`
+            max_chars: 2000,
+            max_batch: 1,
+            prompt_modes: true,
+        }
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        let profile = &self.profile;
+        if profile.api_url.is_empty() || profile.model.is_empty() {
+            return Err(TranslateError::NotConfigured);
+        }
+
+        let (source, target_desc) = langmap::map_pair(self.id(), req)?;
+        let source_desc = source.unwrap_or("auto-detected language");
+
+        let profile_prompt;
+        let template = if req.mode == PromptMode::Translate && !profile.system_prompt.trim().is_empty() {
+            profile_prompt = PromptTemplate {
+                system: profile.system_prompt.clone(),
+                user: req.prompt.user.clone(),
+            };
+            &profile_prompt
+        } else {
+            &req.prompt
+        };
+        let (system_prompt, user_prompt) = prompt::render(template, source_desc, target_desc, req);
+
+        let body = serde_json::json!({
+            "model": profile.model,
+            "messages": [
+                {"role": "system", "content": system_prompt},
+                {"role": "user", "content": user_prompt}
+            ],
+            "temperature": profile.temperature,
+            "max_tokens": profile.max_tokens,
+            "stream": req.on_delta.is_some()
+        });
+
+        let mut request = client.post(&profile.api_url).json(&body);
+        if !profile.api_key.is_empty() {
+            request = request.header("Authorization", format!("Bearer {}", profile.api_key));
+        }
+        let resp = request.send().await?;
+        let resp = check_status(resp).await?;
+
+        // Servers that ignore `stream` still answer with a plain JSON body
+        let is_sse = resp
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/event-stream"));
+
+        if let (true, Some(sink)) = (is_sse, &req.on_delta) {
+            let translated = self.read_stream(resp, sink).await?;
+            return Ok(TranslateResult::ok(
+                self.name(),
+                translated.trim().to_string(),
+                req.source.clone(),
+                &req.target,
+            ));
+        }
+
+        let json = resp.json::<serde_json::Value>().await?;
+
+        let translated = json["choices"][0]["message"]["content"]
+            .as_str()
+            .ok_or_else(|| TranslateError::Parse {
+                message: "missing choices".to_string(),
+            })?
+            .trim()
+            .to_string();
+        Ok(TranslateResult::ok(self.name(), translated, req.source.clone(), &req.target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock::{self, MockServer, Reply};
+
+    fn profile(server: &MockServer) -> LlmProfile {
+        LlmProfile {
+            api_url: format!("{}/v1/chat/completions", server.url),
+            api_key: "sk-test".to_string(),
+            model: "test-model".to_string(),
+            ..LlmProfile::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn streams_deltas_until_done() {
+        let body = concat!(
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\"}}]}\r\n\r\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"Hel\"}}]}\r\n\r\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"lo \"}}]}\r\n\r\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"世界\"}}]}\r\n\r\n",
+            "data: [DONE]\r\n\r\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"ignored\"}}]}\r\n\r\n",
+        )
+        .as_bytes();
+        // Split mid-line and inside "世"
+        let utf8 = body.windows(3).position(|w| w == "世".as_bytes()).unwrap() + 1;
+        let server = MockServer::start(move |_| Reply::sse(&[&body[..70], &body[70..utf8], &body[utf8..]])).await;
+
+        let (req, deltas) = mock::streaming_request("Hello world", "en", "zh-CN");
+        let result = OpenAITranslator::new(&profile(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "Hello 世界");
+        assert_eq!(*deltas.lock().unwrap(), ["Hel", "lo ", "世界"]);
+        let sent = &server.requests()[0];
+        assert_eq!(sent.path, "/v1/chat/completions");
+        assert_eq!(sent.header("authorization"), Some("Bearer sk-test"));
+        assert_eq!(sent.json()["stream"], true);
+        assert_eq!(sent.json()["model"], "test-model");
+    }
+
+    #[tokio::test]
+    async fn plain_json_without_stream() {
+        let server = MockServer::start(|_| {
+            Reply::json(serde_json::json!({"choices": [{"message": {"content": " 你好 "}}]}))
+        })
+        .await;
+
+        let req = mock::request("Hello", "en", "zh-CN");
+        let result = OpenAITranslator::new(&profile(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好");
+        assert_eq!(server.requests()[0].json()["stream"], false);
+    }
+
+    #[tokio::test]
+    async fn error_event_in_stream() {
+        let server = MockServer::start(|_| {
+            Reply::sse(&[b"data: {\"error\":{\"code\":\"overloaded\",\"message\":\"busy\"}}\n\n"])
+        })
+        .await;
+
+        let (req, _) = mock::streaming_request("Hello", "en", "zh-CN");
+        let err = OpenAITranslator::new(&profile(&server))
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, TranslateError::Provider { ref code, .. } if code == "overloaded"));
+    }
+}
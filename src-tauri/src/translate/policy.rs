@@ -0,0 +1,384 @@
+use super::{Capabilities, StreamDelta, TranslateError, TranslateRequest, TranslateResult, Translator};
+use crate::config::RequestPolicy;
+use async_trait::async_trait;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+// ==================== Request Policy ====================
+
+/// A `Retry-After` longer than this is reported instead of waited out
+const MAX_RETRY_AFTER_SECS: u64 = 30;
+const MAX_BACKOFF: Duration = Duration::from_secs(8);
+
+/// Token bucket shared by all requests to one service
+struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    /// Available tokens and when they were last topped up
+    state: Mutex<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, burst: u32) -> Self {
+        let burst = f64::from(burst.max(1));
+        Self {
+            rate,
+            burst,
+            state: Mutex::new((burst, Instant::now())),
+        }
+    }
+
+    fn matches(&self, policy: &RequestPolicy) -> bool {
+        self.rate == policy.rate_per_sec && self.burst == f64::from(policy.burst.max(1))
+    }
+
+    /// Wait until a token is available and take it
+    async fn acquire(&self) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                let now = Instant::now();
+                let refill = now.duration_since(state.1).as_secs_f64() * self.rate;
+                *state = ((state.0 + refill).min(self.burst), now);
+                if state.0 >= 1.0 {
+                    state.0 -= 1.0;
+                    return;
+                }
+                Duration::from_secs_f64((1.0 - state.0) / self.rate)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Buckets outlive a single translation, so they are kept per service instance
+/// and only replaced when that instance's rate settings change
+static BUCKETS: OnceLock<Mutex<HashMap<String, Arc<TokenBucket>>>> = OnceLock::new();
+
+fn bucket_for(id: &str, policy: &RequestPolicy) -> Option<Arc<TokenBucket>> {
+    if policy.rate_per_sec <= 0.0 {
+        return None;
+    }
+    let mut buckets = BUCKETS.get_or_init(Default::default).lock().unwrap();
+    match buckets.get(id) {
+        Some(bucket) if bucket.matches(policy) => Some(bucket.clone()),
+        _ => {
+            let bucket = Arc::new(TokenBucket::new(policy.rate_per_sec, policy.burst));
+            buckets.insert(id.to_string(), bucket.clone());
+            Some(bucket)
+        }
+    }
+}
+
+/// Delay before the next attempt: the server's `Retry-After` when given,
+/// otherwise exponential backoff with jitter. `None` means give up.
+fn backoff(attempt: u32, error: &TranslateError) -> Option<Duration> {
+    if let TranslateError::RateLimit {
+        retry_after: Some(secs),
+    } = error
+    {
+        return (*secs <= MAX_RETRY_AFTER_SECS).then(|| Duration::from_secs(*secs));
+    }
+    let base = (Duration::from_millis(500) * 2u32.saturating_pow(attempt)).min(MAX_BACKOFF);
+    let jitter = base.mul_f64(rand::random::<f64>() * 0.5);
+    Some(base + jitter)
+}
+
+/// Wraps a translator with its service's timeout, retries and rate limit
+pub struct Guarded {
+    inner: Box<dyn Translator>,
+    policy: RequestPolicy,
+    bucket: Option<Arc<TokenBucket>>,
+}
+
+impl Guarded {
+    pub fn new(inner: Box<dyn Translator>, policy: RequestPolicy) -> Self {
+        let bucket = bucket_for(&inner.instance_key(), &policy);
+        Self {
+            inner,
+            policy,
+            bucket,
+        }
+    }
+
+    /// One call under the rate limit and timeout
+    async fn attempt<T>(&self, call: impl Future<Output = Result<T, TranslateError>>) -> Result<T, TranslateError> {
+        if let Some(bucket) = &self.bucket {
+            bucket.acquire().await;
+        }
+        if self.policy.timeout_secs == 0 {
+            return call.await;
+        }
+        let timeout = Duration::from_secs(self.policy.timeout_secs);
+        tokio::time::timeout(timeout, call)
+            .await
+            .unwrap_or(Err(TranslateError::Timeout))
+    }
+
+    /// Attempts of `call` until it succeeds, fails for good, or `streamed`
+    /// says output already reached the UI
+    async fn with_retries<T, F, Fut>(&self, call: F, streamed: &AtomicBool) -> Result<T, TranslateError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, TranslateError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let error = match self.attempt(call()).await {
+                Ok(result) => return Ok(result),
+                Err(e) => e,
+            };
+            if attempt >= self.policy.max_retries
+                || !error.is_retryable()
+                || streamed.load(Ordering::Relaxed)
+            {
+                return Err(error);
+            }
+            let Some(delay) = backoff(attempt, &error) else {
+                return Err(error);
+            };
+            eprintln!("[{}] Retrying in {:?} after: {}", self.name(), delay, error);
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+#[async_trait]
+impl Translator for Guarded {
+    fn id(&self) -> &'static str {
+        self.inner.id()
+    }
+
+    fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    fn instance_key(&self) -> String {
+        self.inner.instance_key()
+    }
+
+    fn supported_languages(&self) -> Vec<&'static str> {
+        self.inner.supported_languages()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.inner.capabilities()
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        // Once partial output has reached the UI a retry would repeat it, so stop retrying
+        let streamed = Arc::new(AtomicBool::new(false));
+        let tracked;
+        let req = match &req.on_delta {
+            Some(sink) => {
+                let sink = sink.clone();
+                let flag = streamed.clone();
+                tracked = TranslateRequest {
+                    on_delta: Some(Arc::new(move |d: StreamDelta| {
+                        flag.store(true, Ordering::Relaxed);
+                        sink(d)
+                    })),
+                    ..req.clone()
+                };
+                &tracked
+            }
+            None => req,
+        };
+
+        self.with_retries(|| self.inner.translate(client, req), &streamed)
+            .await
+    }
+
+    async fn translate_batch(
+        &self,
+        client: &Client,
+        req: &TranslateRequest,
+        texts: &[String],
+    ) -> Result<Vec<TranslateResult>, TranslateError> {
+        self.with_retries(|| self.inner.translate_batch(client, req, texts), &AtomicBool::new(false))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LibreServiceEntry;
+    use crate::translate::libre::LibreTranslator;
+    use crate::translate::mock::{self, MockServer, Reply};
+    use serde_json::json;
+    use std::sync::atomic::AtomicUsize;
+
+    fn policy(max_retries: u32) -> RequestPolicy {
+        RequestPolicy {
+            timeout_secs: 5,
+            max_retries,
+            rate_per_sec: 0.0,
+            burst: 1,
+        }
+    }
+
+    /// A LibreTranslate mock answering with `failures` in turn, then success
+    async fn server(failures: Vec<Reply>) -> MockServer {
+        let failures = Mutex::new(failures.into_iter());
+        MockServer::start(move |_| {
+            failures
+                .lock()
+                .unwrap()
+                .next()
+                .unwrap_or_else(|| Reply::json(json!({"translatedText": "hallo"})))
+        })
+        .await
+    }
+
+    async fn translate(server: &MockServer, policy: RequestPolicy) -> Result<TranslateResult, TranslateError> {
+        let entry = LibreServiceEntry {
+            enabled: true,
+            base_url: server.url.clone(),
+            api_key: String::new(),
+        };
+        let guarded = Guarded::new(Box::new(LibreTranslator::new(&entry)), policy);
+        guarded.translate(&Client::new(), &mock::request("hello", "en", "de")).await
+    }
+
+    fn rate_limited(retry_after: &str) -> Reply {
+        Reply::status(429, json!({"error": "Too many requests"})).header("Retry-After", retry_after)
+    }
+
+    #[tokio::test]
+    async fn bucket_allows_burst_then_throttles() {
+        let bucket = TokenBucket::new(20.0, 3);
+        let start = Instant::now();
+        for _ in 0..3 {
+            bucket.acquire().await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(20));
+        bucket.acquire().await;
+        // One token takes 1/20 s to come back
+        assert!(start.elapsed() >= Duration::from_millis(45));
+    }
+
+    #[test]
+    fn buckets_are_replaced_when_settings_change() {
+        let mut policy = policy(0);
+        assert!(bucket_for("test:off", &policy).is_none());
+        policy.rate_per_sec = 2.0;
+        let first = bucket_for("test:bucket", &policy).unwrap();
+        assert!(Arc::ptr_eq(&first, &bucket_for("test:bucket", &policy).unwrap()));
+        policy.burst = 4;
+        assert!(!Arc::ptr_eq(&first, &bucket_for("test:bucket", &policy).unwrap()));
+    }
+
+    #[test]
+    fn backoff_honours_retry_after_up_to_the_cap() {
+        let limited = |secs| TranslateError::RateLimit { retry_after: Some(secs) };
+        assert_eq!(backoff(0, &limited(3)), Some(Duration::from_secs(3)));
+        assert_eq!(backoff(0, &limited(MAX_RETRY_AFTER_SECS)), Some(Duration::from_secs(30)));
+        assert_eq!(backoff(0, &limited(MAX_RETRY_AFTER_SECS + 1)), None);
+
+        let first = backoff(0, &TranslateError::Timeout).unwrap();
+        assert!(first >= Duration::from_millis(500) && first <= Duration::from_millis(750));
+        let late = backoff(20, &TranslateError::Timeout).unwrap();
+        assert!(late >= MAX_BACKOFF && late <= MAX_BACKOFF.mul_f64(1.5));
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors() {
+        let server = server(vec![Reply::status(503, json!({"error": "unavailable"}))]).await;
+        let result = translate(&server, policy(1)).await.unwrap();
+        assert_eq!(result.translated, "hallo");
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn retries_rate_limits_after_retry_after() {
+        let server = server(vec![rate_limited("0"), rate_limited("0")]).await;
+        let result = translate(&server, policy(2)).await.unwrap();
+        assert_eq!(result.translated, "hallo");
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn retries_are_bounded() {
+        let server = server((0..10).map(|_| rate_limited("0")).collect()).await;
+        let err = translate(&server, policy(2)).await.unwrap_err();
+        assert!(matches!(err, TranslateError::RateLimit { retry_after: Some(0) }));
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn long_retry_after_is_reported() {
+        let server = server(vec![rate_limited("120")]).await;
+        let err = translate(&server, policy(2)).await.unwrap_err();
+        assert!(matches!(err, TranslateError::RateLimit { retry_after: Some(120) }));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn auth_and_quota_errors_are_not_retried() {
+        for (status, check) in [
+            (401, (|e: &TranslateError| matches!(e, TranslateError::Auth { .. })) as fn(&TranslateError) -> bool),
+            (456, |e: &TranslateError| matches!(e, TranslateError::Quota { .. })),
+            (400, |e: &TranslateError| matches!(e, TranslateError::Http { status: 400, .. })),
+        ] {
+            let server = server(vec![Reply::status(status, json!({"error": "no"}))]).await;
+            let err = translate(&server, policy(2)).await.unwrap_err();
+            assert!(check(&err), "{}: {:?}", status, err);
+            assert_eq!(server.requests().len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn streamed_output_is_not_retried() {
+        struct Flaky(Arc<AtomicUsize>);
+
+        #[async_trait]
+        impl Translator for Flaky {
+            fn id(&self) -> &'static str {
+                "flaky"
+            }
+
+            fn name(&self) -> String {
+                "Flaky".to_string()
+            }
+
+            fn capabilities(&self) -> Capabilities {
+                Capabilities {
+                    requires_credentials: false,
+                    detects_language: false,
+                    streaming: true,
+                    dictionary: false,
+                    glossary: false,
+                    max_chars: 100,
+                    max_batch: 1,
+                    prompt_modes: false,
+                }
+            }
+
+            async fn translate(&self, _: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+                self.0.fetch_add(1, Ordering::SeqCst);
+                if let Some(sink) = &req.on_delta {
+                    sink(StreamDelta {
+                        service: self.name(),
+                        delta: "par".to_string(),
+                    });
+                }
+                Err(TranslateError::Network {
+                    message: "connection reset".to_string(),
+                })
+            }
+        }
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let guarded = Guarded::new(Box::new(Flaky(calls.clone())), policy(2));
+        let (req, deltas) = mock::streaming_request("hello", "en", "de");
+        assert!(guarded.translate(&Client::new(), &req).await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(*deltas.lock().unwrap(), ["par"]);
+    }
+}
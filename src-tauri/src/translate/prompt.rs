@@ -0,0 +1,278 @@
+use super::TranslateRequest;
+use crate::config::{PromptConfig, PromptTemplate};
+use crate::glossary::GlossaryEntry;
+use serde::{Deserialize, Serialize};
+
+// ==================== LLM Prompts ====================
+
+/// What the LLM services do with the text. Every mode but `Translate` is
+/// LLM-only; the other services skip such requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptMode {
+    #[default]
+    Translate,
+    Polish,
+    Grammar,
+    Summarize,
+    Formal,
+    Informal,
+}
+
+impl PromptMode {
+    pub const ALL: [PromptMode; 6] = [
+        PromptMode::Translate,
+        PromptMode::Polish,
+        PromptMode::Grammar,
+        PromptMode::Summarize,
+        PromptMode::Formal,
+        PromptMode::Informal,
+    ];
+
+    /// Key in `PromptConfig::templates`, same as the serialized name
+    pub fn id(self) -> &'static str {
+        match self {
+            PromptMode::Translate => "translate",
+            PromptMode::Polish => "polish",
+            PromptMode::Grammar => "grammar",
+            PromptMode::Summarize => "summarize",
+            PromptMode::Formal => "formal",
+            PromptMode::Informal => "informal",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            PromptMode::Translate => "翻译",
+            PromptMode::Polish => "润色",
+            PromptMode::Grammar => "语法解析",
+            PromptMode::Summarize => "摘要",
+            PromptMode::Formal => "正式语气",
+            PromptMode::Informal => "口语化",
+        }
+    }
+
+    fn default_system(self) -> &'static str {
+        match self {
+            PromptMode::Translate => {
+                "You are a professional translator. Translate the following text from {source} to {target}. \
+                 Only output the translation, no explanations or extra text."
+            }
+            PromptMode::Polish => {
+                "You are an experienced editor. Improve the fluency, grammar and word choice of the following \
+                 text without changing its meaning, and keep it in its original language. \
+                 Only output the revised text."
+            }
+            PromptMode::Grammar => {
+                "You are a language teacher. Explain the grammar of the following {source} text in {target}: \
+                 break it into its parts, name the structures used and point out anything a learner could \
+                 trip over. Be concise."
+            }
+            PromptMode::Summarize => {
+                "Summarize the following text in {target} in a few sentences, keeping the key facts. \
+                 Only output the summary."
+            }
+            PromptMode::Formal => {
+                "You are a professional translator. Translate the following text from {source} to {target} \
+                 in a formal, polite register suitable for business writing. Only output the translation."
+            }
+            PromptMode::Informal => {
+                "You are a professional translator. Translate the following text from {source} to {target} \
+                 in a casual, conversational tone, as a native speaker would say it to a friend. \
+                 Only output the translation."
+            }
+        }
+    }
+}
+
+/// A mode with its built-in template, for the settings page
+#[derive(Debug, Clone, Serialize)]
+pub struct PromptPreset {
+    pub mode: PromptMode,
+    pub name: &'static str,
+    pub system: &'static str,
+    pub user: &'static str,
+}
+
+pub fn prompt_presets() -> Vec<PromptPreset> {
+    PromptMode::ALL
+        .iter()
+        .map(|&mode| PromptPreset {
+            mode,
+            name: mode.label(),
+            system: mode.default_system(),
+            user: "{text}",
+        })
+        .collect()
+}
+
+/// The template for `mode`: the user's override where it is not blank,
+/// otherwise the built-in one
+pub fn resolve_template(mode: PromptMode, prompts: &PromptConfig) -> PromptTemplate {
+    let custom = prompts.templates.get(mode.id());
+    let pick = |custom: Option<&String>, default: &str| match custom {
+        Some(s) if !s.trim().is_empty() => s.clone(),
+        _ => default.to_string(),
+    };
+    PromptTemplate {
+        system: pick(custom.map(|t| &t.system), mode.default_system()),
+        user: pick(custom.map(|t| &t.user), "{text}"),
+    }
+}
+
+fn glossary_lines(glossary: &[GlossaryEntry]) -> String {
+    glossary
+        .iter()
+        .map(|entry| {
+            if entry.translation.is_empty() {
+                format!("- {} (keep untranslated)", entry.term)
+            } else {
+                format!("- {} -> {}", entry.term, entry.translation)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Replace each `{name}` in `template` with its value in a single pass, so
+/// braces inside the values are never read as variables. Unknown names stay as written.
+fn substitute(template: &str, vars: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let value = rest[1..].find('}').and_then(|end| {
+            let name = &rest[1..end + 1];
+            vars.iter().find(|(var, _)| *var == name).map(|(_, value)| (end + 2, *value))
+        });
+        match value {
+            Some((len, value)) => {
+                out.push_str(value);
+                rest = &rest[len..];
+            }
+            None => {
+                out.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// System and user messages for `req`, with `{source}`, `{target}`, `{text}`,
+/// `{glossary}` and `{context}` filled in. A glossary or context the template
+/// does not place itself is appended to the system prompt.
+pub fn render(template: &PromptTemplate, source: &str, target: &str, req: &TranslateRequest) -> (String, String) {
+    let glossary = glossary_lines(&req.glossary);
+    let vars = [
+        ("source", source),
+        ("target", target),
+        ("glossary", glossary.as_str()),
+        ("context", req.context.as_str()),
+        ("text", req.text.as_str()),
+    ];
+    let fill = |s: &str| substitute(s, &vars);
+    let mentions = |var: &str| template.system.contains(var) || template.user.contains(var);
+
+    let mut system = fill(&template.system);
+    if !glossary.is_empty() && !mentions("{glossary}") {
+        system.push_str("\nFollow this glossary strictly:\n");
+        system.push_str(&glossary);
+    }
+    if !req.context.is_empty() && !mentions("{context}") {
+        system.push_str("\nSurrounding context, for reference only (do not output it):\n");
+        system.push_str(&req.context);
+    }
+    (system, fill(&template.user))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock;
+
+    fn entry(term: &str, translation: &str) -> GlossaryEntry {
+        GlossaryEntry {
+            id: 1,
+            source_lang: "*".to_string(),
+            target_lang: "*".to_string(),
+            term: term.to_string(),
+            translation: translation.to_string(),
+        }
+    }
+
+    fn template(system: &str, user: &str) -> PromptTemplate {
+        PromptTemplate {
+            system: system.to_string(),
+            user: user.to_string(),
+        }
+    }
+
+    #[test]
+    fn presets_fill_every_variable() {
+        let req = mock::request("Good morning", "en", "de");
+        for mode in PromptMode::ALL {
+            let (system, user) = render(&resolve_template(mode, &PromptConfig::default()), "English", "German", &req);
+            assert!(!system.contains('{') && !user.contains('{'), "{:?}: {}", mode, system);
+            assert_eq!(user, "Good morning");
+            if mode != PromptMode::Polish {
+                assert!(system.contains("German"), "{:?}: {}", mode, system);
+            }
+        }
+        let (system, _) = render(&resolve_template(PromptMode::Grammar, &PromptConfig::default()), "English", "German", &req);
+        assert!(system.contains("English text in German"));
+    }
+
+    #[test]
+    fn custom_templates_override_only_non_blank_fields() {
+        let mut prompts = PromptConfig::default();
+        prompts.templates.insert("formal".to_string(), template("  ", "Text: {text}"));
+        let resolved = resolve_template(PromptMode::Formal, &prompts);
+        assert_eq!(resolved.system, PromptMode::Formal.default_system());
+        assert_eq!(resolved.user, "Text: {text}");
+    }
+
+    #[test]
+    fn values_are_not_substituted_again() {
+        let mut req = mock::request("Use {target} here", "en", "de");
+        req.glossary = vec![entry("{text}", "")];
+        req.context = "See {glossary}".to_string();
+        let (system, user) = render(
+            &template("To {target}.\n{glossary}\n{context}", "{text}"),
+            "English",
+            "German",
+            &req,
+        );
+        assert_eq!(system, "To German.\n- {text} (keep untranslated)\nSee {glossary}");
+        assert_eq!(user, "Use {target} here");
+    }
+
+    #[test]
+    fn unplaced_glossary_and_context_are_appended() {
+        let mut req = mock::request("hello", "en", "de");
+        req.glossary = vec![entry("Tauri", ""), entry("window", "Fenster")];
+        req.context = "A desktop app".to_string();
+        let (system, _) = render(&template("Translate to {target}.", "{text}"), "English", "German", &req);
+        assert_eq!(
+            system,
+            "Translate to German.\nFollow this glossary strictly:\n- Tauri (keep untranslated)\n- window -> Fenster\n\
+             Surrounding context, for reference only (do not output it):\nA desktop app"
+        );
+    }
+
+    #[test]
+    fn empty_and_unknown_variables() {
+        let req = mock::request("hello", "en", "de");
+        let (system, user) = render(
+            &template("{source}->{target} [{glossary}] [{context}] {unknown} {", "{text} {{text}}"),
+            "",
+            "German",
+            &req,
+        );
+        // Nothing is appended for an empty glossary or context
+        assert_eq!(system, "->German [] [] {unknown} {");
+        assert_eq!(user, "hello {hello}");
+    }
+}
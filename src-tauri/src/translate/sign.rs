@@ -0,0 +1,22 @@
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+// ==================== Request Signing ====================
+
+/// Lowercase hex SHA-256, as used in canonical requests and Youdao's `sign`
+pub fn sha256_hex(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
+
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Chain of HMACs deriving a signing key: each step keys the next one
+pub fn derive_key(secret: &[u8], steps: &[&str]) -> Vec<u8> {
+    steps
+        .iter()
+        .fold(secret.to_vec(), |key, step| hmac_sha256(&key, step.as_bytes()))
+}
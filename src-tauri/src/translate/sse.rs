@@ -0,0 +1,67 @@
+// ==================== Server-Sent Events ====================
+
+/// Incremental parser for `text/event-stream` response bodies.
+/// Chunks may split lines (and UTF-8 sequences) anywhere, so bytes are
+/// buffered until a full line is available.
+#[derive(Default)]
+pub struct SseParser {
+    buf: Vec<u8>,
+}
+
+impl SseParser {
+    /// Feed one body chunk, returning the `data:` payload of every completed line
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buf.extend_from_slice(chunk);
+
+        let mut out = Vec::new();
+        while let Some(pos) = self.buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buf.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\r', '\n']);
+            if let Some(data) = line.strip_prefix("data:") {
+                out.push(data.trim_start().to_string());
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STREAM: &str = "event: message\r\ndata: {\"t\":\"你好\"}\r\n\r\n: keep-alive\r\ndata: [DONE]\r\n\r\n";
+
+    fn feed_in(pieces: &[&[u8]]) -> Vec<String> {
+        let mut parser = SseParser::default();
+        pieces.iter().flat_map(|p| parser.feed(p)).collect()
+    }
+
+    #[test]
+    fn whole_body() {
+        assert_eq!(feed_in(&[STREAM.as_bytes()]), ["{\"t\":\"你好\"}", "[DONE]"]);
+    }
+
+    #[test]
+    fn split_mid_line_and_mid_utf8() {
+        let bytes = STREAM.as_bytes();
+        // Inside "data:", inside the 3-byte "你", and between '\r' and '\n'
+        let utf8 = STREAM.find('你').unwrap() + 1;
+        let crlf = STREAM.find("\r\n\r\n: keep").unwrap() + 1;
+        let pieces = [&bytes[..18], &bytes[18..utf8], &bytes[utf8..crlf], &bytes[crlf..]];
+        assert_eq!(feed_in(&pieces), ["{\"t\":\"你好\"}", "[DONE]"]);
+    }
+
+    #[test]
+    fn one_byte_at_a_time() {
+        let pieces: Vec<&[u8]> = STREAM.as_bytes().chunks(1).collect();
+        assert_eq!(feed_in(&pieces), ["{\"t\":\"你好\"}", "[DONE]"]);
+    }
+
+    #[test]
+    fn incomplete_line_waits() {
+        let mut parser = SseParser::default();
+        assert!(parser.feed(b"data: par").is_empty());
+        assert_eq!(parser.feed(b"tial\n"), ["partial"]);
+    }
+}
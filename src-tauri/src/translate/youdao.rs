@@ -0,0 +1,263 @@
+use super::langmap;
+use super::sign::sha256_hex;
+use super::{base_url_or, check_status, Capabilities, TranslateError, TranslateRequest, TranslateResult, Translator};
+use crate::config::YoudaoServiceEntry;
+use crate::dictionary::{self, DictionaryEntry, Meaning};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde_json::Value;
+
+// ==================== Youdao Translate ====================
+
+const API_URL: &str = "https://openapi.youdao.com";
+
+pub struct YoudaoTranslator {
+    app_key: String,
+    app_secret: String,
+    base_url: String,
+}
+
+impl YoudaoTranslator {
+    pub fn new(entry: &YoudaoServiceEntry) -> Self {
+        Self {
+            app_key: entry.app_key.clone(),
+            app_secret: entry.app_secret.clone(),
+            base_url: base_url_or(&entry.base_url, API_URL),
+        }
+    }
+}
+
+/// The part of `q` that goes into the signature: the text itself up to 20
+/// chars, otherwise its first 10 chars, char count and last 10 chars
+fn sign_input(q: &str) -> String {
+    let chars: Vec<char> = q.chars().collect();
+    if chars.len() <= 20 {
+        return q.to_string();
+    }
+    let head: String = chars[..10].iter().collect();
+    let tail: String = chars[chars.len() - 10..].iter().collect();
+    format!("{}{}{}", head, chars.len(), tail)
+}
+
+/// `signType=v3`: sha256(appKey + input + salt + curtime + appSecret)
+fn sign_v3(app_key: &str, q: &str, salt: &str, curtime: &str, app_secret: &str) -> String {
+    sha256_hex(format!("{}{}{}{}{}", app_key, sign_input(q), salt, curtime, app_secret).as_bytes())
+}
+
+/// Map Youdao's `errorCode` values to error kinds
+fn youdao_error(code: &str) -> TranslateError {
+    match code {
+        "108" | "110" | "111" | "202" => TranslateError::Auth {
+            message: format!("Youdao error {}", code),
+        },
+        "401" => TranslateError::Quota {
+            message: "account balance exhausted".to_string(),
+        },
+        "411" | "412" => TranslateError::RateLimit { retry_after: None },
+        "102" => TranslateError::UnsupportedLanguage { lang: code.to_string() },
+        _ => TranslateError::Provider {
+            code: code.to_string(),
+            message: "Youdao request failed".to_string(),
+        },
+    }
+}
+
+/// Build a dictionary entry from the `basic` block (phonetics, "n. ..."
+/// explanations) and the `web` phrases
+fn parse_dictionary(json: &Value, word: &str) -> Option<DictionaryEntry> {
+    let basic = json.get("basic")?;
+    let mut entry = DictionaryEntry {
+        word: word.to_string(),
+        source: "Youdao".to_string(),
+        ..Default::default()
+    };
+
+    for (key, label) in [("uk-phonetic", "英 "), ("us-phonetic", "美 ")] {
+        if let Some(p) = basic[key].as_str().filter(|p| !p.is_empty()) {
+            entry.phonetics.push(format!("{}/{}/", label, p));
+        }
+    }
+    // Plain `phonetic` repeats one of the above for English words
+    if entry.phonetics.is_empty() {
+        if let Some(p) = basic["phonetic"].as_str().filter(|p| !p.is_empty()) {
+            entry.phonetics.push(format!("/{}/", p));
+        }
+    }
+
+    // "n. 苹果；苹果树", or just "苹果" when there is no part of speech
+    for explain in basic["explains"].as_array().into_iter().flatten().filter_map(Value::as_str) {
+        let (pos, defs) = match explain.split_once(". ") {
+            Some((pos, defs)) if pos.len() <= 6 && pos.chars().all(|c| c.is_ascii_alphabetic() || c == '.') => {
+                (format!("{}.", pos), defs)
+            }
+            _ => (String::new(), explain),
+        };
+        entry.meanings.push(Meaning {
+            part_of_speech: pos,
+            definitions: defs
+                .split(['；', ';'])
+                .map(|d| dictionary::strip_markup(d.trim()))
+                .filter(|d| !d.is_empty())
+                .collect(),
+        });
+    }
+
+    for phrase in json["web"].as_array().into_iter().flatten() {
+        let (Some(key), Some(values)) = (phrase["key"].as_str(), phrase["value"].as_array()) else {
+            continue;
+        };
+        // The first web entry is usually the word itself
+        if key.eq_ignore_ascii_case(word) {
+            continue;
+        }
+        let values: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+        entry.examples.push(format!("{}: {}", key, values.join("; ")));
+    }
+
+    (!entry.meanings.is_empty()).then_some(entry)
+}
+
+#[async_trait]
+impl Translator for YoudaoTranslator {
+    fn id(&self) -> &'static str {
+        "youdao"
+    }
+
+    fn name(&self) -> String {
+        "Youdao".to_string()
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            requires_credentials: true,
+            detects_language: true,
+            streaming: false,
+            dictionary: true,
+            glossary: false,
+            // Youdao limits `q` to 5000 chars
+            max_chars: 4500,
+            max_batch: 1,
+            prompt_modes: false,
+        }
+    }
+
+    async fn translate(&self, client: &Client, req: &TranslateRequest) -> Result<TranslateResult, TranslateError> {
+        if self.app_key.is_empty() || self.app_secret.is_empty() {
+            return Err(TranslateError::NotConfigured);
+        }
+
+        let (from, to) = langmap::map_pair(self.id(), req)?;
+        let salt = format!("{:x}", rand::random::<u64>());
+        let curtime = chrono::Utc::now().timestamp().to_string();
+        let sign = sign_v3(&self.app_key, &req.text, &salt, &curtime, &self.app_secret);
+
+        let params = [
+            ("q", req.text.as_str()),
+            ("from", from.unwrap_or("auto")),
+            ("to", to),
+            ("appKey", &self.app_key),
+            ("salt", &salt),
+            ("sign", &sign),
+            ("signType", "v3"),
+            ("curtime", &curtime),
+        ];
+
+        let resp = client
+            .post(format!("{}/api", self.base_url))
+            .form(&params)
+            .send()
+            .await?;
+        let json = check_status(resp).await?.json::<Value>().await?;
+
+        let code = json["errorCode"].as_str().unwrap_or("0");
+        if code != "0" {
+            return Err(youdao_error(code));
+        }
+        let translated = json["translation"]
+            .as_array()
+            .ok_or_else(|| TranslateError::Parse {
+                message: "missing translation".to_string(),
+            })?
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join("\n");
+        // `l` is "<from>2<to>", e.g. "en2zh-CHS"
+        let detected = json["l"]
+            .as_str()
+            .and_then(|l| l.split_once('2'))
+            .map(|(from, _)| langmap::from_service_code(self.id(), from))
+            .unwrap_or_else(|| req.source.clone());
+        let mut result = TranslateResult::ok(self.name(), translated, detected, &req.target);
+        if dictionary::looks_like_word(&req.text) {
+            result.dictionary = parse_dictionary(&json, req.text.trim());
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translate::mock::{self, MockServer, Reply};
+
+    #[test]
+    fn sign_input_truncates_long_text() {
+        assert_eq!(sign_input(""), "");
+        assert_eq!(sign_input("hello"), "hello");
+        // Exactly 20 chars is kept whole
+        assert_eq!(sign_input("abcdefghijklmnopqrst"), "abcdefghijklmnopqrst");
+        assert_eq!(sign_input("abcdefghijklmnopqrstu"), "abcdefghij21lmnopqrstu");
+        assert_eq!(sign_input("abcdefghijklmnopqrstuvwxyz"), "abcdefghij26qrstuvwxyz");
+        // Counted in chars, not bytes
+        let chinese = "一二三四五六七八九十甲乙丙丁戊己庚辛壬癸子";
+        assert_eq!(sign_input(chinese), "一二三四五六七八九十21乙丙丁戊己庚辛壬癸子");
+        assert_eq!(sign_input(&chinese[..chinese.len() - 3]), &chinese[..chinese.len() - 3]);
+    }
+
+    #[test]
+    fn sign_v3_hashes_truncated_input() {
+        // Youdao's docs give the formula, sha256(appKey + input + salt + curtime + appSecret),
+        // but no worked example, and no live signed request has been captured yet.
+        // The expected value was computed separately (Python `hashlib`) from that formula.
+        assert_eq!(
+            sign_v3("appkey", "hello", "salt", "1700000000", "secret"),
+            "410d75111b6c4ce44e3293b69c0f6f84d905f5c1a5dc8fbdd8cc143672523f42"
+        );
+        assert_eq!(
+            sign_v3("appkey", "abcdefghijklmnopqrstuvwxyz", "salt", "1700000000", "secret"),
+            sha256_hex(b"appkeyabcdefghij26qrstuvwxyzsalt1700000000secret")
+        );
+    }
+
+    #[tokio::test]
+    async fn honours_base_url() {
+        let server = MockServer::start(|_| {
+            Reply::json(serde_json::json!({"errorCode": "0", "translation": ["你好，世界"], "l": "en2zh-CHS"}))
+        })
+        .await;
+        let entry = YoudaoServiceEntry {
+            enabled: true,
+            app_key: "appkey".to_string(),
+            app_secret: "secret".to_string(),
+            base_url: format!("{}/proxy/", server.url),
+        };
+
+        let text = "Hello, world, from a sentence longer than twenty chars";
+        let req = mock::request(text, "auto", "zh-CN");
+        let result = YoudaoTranslator::new(&entry)
+            .translate(&Client::new(), &req)
+            .await
+            .unwrap();
+
+        assert_eq!(result.translated, "你好，世界");
+        assert_eq!(result.source_lang, "en");
+        let sent = &server.requests()[0];
+        assert_eq!(sent.path, "/proxy/api");
+        assert_eq!(sent.form("q").as_deref(), Some(text));
+        assert_eq!(sent.form("to").as_deref(), Some("zh-CHS"));
+        let salt = sent.form("salt").unwrap();
+        let curtime = sent.form("curtime").unwrap();
+        assert_eq!(sent.form("sign"), Some(sign_v3("appkey", text, &salt, &curtime, "secret")));
+    }
+}
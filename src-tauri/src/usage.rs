@@ -0,0 +1,106 @@
+use crate::config::data_dir;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Months kept in `usage.json`, newest first
+const KEEP_MONTHS: usize = 12;
+
+/// What one service was sent in one month
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServiceUsage {
+    /// Characters of source text sent in successful requests
+    pub characters: u64,
+    pub requests: u64,
+    /// Count and limit the provider reports itself (DeepL `/v2/usage`); they
+    /// cover the whole billing period, other apps using the key included
+    pub provider_count: Option<u64>,
+    pub provider_limit: Option<u64>,
+    /// The threshold warning already went out this month
+    pub warned: bool,
+}
+
+impl ServiceUsage {
+    /// Characters counted against the limit: the larger of ours and the provider's
+    pub fn used(&self) -> u64 {
+        self.provider_count.map_or(self.characters, |c| c.max(self.characters))
+    }
+}
+
+/// Characters sent per service per month, persisted as `usage.json`.
+/// Services are keyed by instance key ("deepl", "openai:<profile id>").
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Usage {
+    /// "YYYY-MM" -> service key -> usage
+    months: BTreeMap<String, BTreeMap<String, ServiceUsage>>,
+}
+
+fn usage_path() -> PathBuf {
+    data_dir().join("usage.json")
+}
+
+/// The current month in local time, as "YYYY-MM"
+pub fn current_month() -> String {
+    chrono::Local::now().format("%Y-%m").to_string()
+}
+
+impl Usage {
+    pub fn load() -> Self {
+        let data = fs::read_to_string(usage_path()).unwrap_or_default();
+        serde_json::from_str(&data).unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Ok(data) = serde_json::to_string_pretty(self) {
+            fs::write(usage_path(), data).ok();
+        }
+    }
+
+    /// This month's entry for `key`, dropping months past `KEEP_MONTHS`
+    fn entry(&mut self, key: &str) -> &mut ServiceUsage {
+        let month = current_month();
+        if !self.months.contains_key(&month) {
+            self.months.insert(month.clone(), BTreeMap::new());
+            while self.months.len() > KEEP_MONTHS {
+                self.months.pop_first();
+            }
+        }
+        self.months
+            .get_mut(&month)
+            .unwrap()
+            .entry(key.to_string())
+            .or_default()
+    }
+
+    /// Count one successful request of `characters` and return the month's total
+    pub fn record(&mut self, key: &str, characters: u64) -> ServiceUsage {
+        let entry = self.entry(key);
+        entry.characters += characters;
+        entry.requests += 1;
+        let snapshot = entry.clone();
+        self.save();
+        snapshot
+    }
+
+    /// Store the provider's own count and limit
+    pub fn set_reported(&mut self, key: &str, count: u64, limit: u64) -> ServiceUsage {
+        let entry = self.entry(key);
+        entry.provider_count = Some(count);
+        entry.provider_limit = Some(limit);
+        let snapshot = entry.clone();
+        self.save();
+        snapshot
+    }
+
+    pub fn mark_warned(&mut self, key: &str) {
+        self.entry(key).warned = true;
+        self.save();
+    }
+
+    /// Every service's usage in `month` ("YYYY-MM")
+    pub fn month(&self, month: &str) -> BTreeMap<String, ServiceUsage> {
+        self.months.get(month).cloned().unwrap_or_default()
+    }
+}
@@ -14,6 +14,7 @@ const $ = (sel) => document.querySelector(sel);
 const inputText = $('#inputText');
 const sourceLang = $('#sourceLang');
 const targetLang = $('#targetLang');
+const promptMode = $('#promptMode');
 const resultsSection = $('#resultsSection');
 const loadingBar = $('#loadingBar');
 const mainView = $('#mainView');
@@ -23,14 +24,26 @@ const settingsView = $('#settingsView');
 let config = null;
 let isPinned = false;
 let isTranslating = false;
+let currentRequestId = 0;
+let streamingCards = new Map();
 let isOcrInProgress = false;
+let languages = [];
+let rtlLanguages = new Set();
+let promptPresets = [];
+// Template overrides by mode, edited one mode at a time in settings
+let promptTemplates = {};
+let editingMode = 'translate';
 
 // ==================== Init ====================
 async function init() {
   config = await invoke('get_config');
-  const languages = await invoke('get_languages');
+  languages = await invoke('get_languages');
+  rtlLanguages = new Set(languages.filter(l => l.rtl).map(l => l.code));
 
-  populateLanguageSelects(languages);
+  promptPresets = await invoke('get_prompt_presets');
+
+  await populateLanguageSelects();
+  populatePromptModes();
   applyConfig(config);
   applyTheme(config.general.theme);
   showEmptyState();
@@ -40,37 +53,177 @@ async function init() {
   inputText.focus();
 }
 
-function populateLanguageSelects(languages) {
+// Only languages at least one enabled service accepts are offered
+async function populateLanguageSelects() {
+  const services = await invoke('get_services');
+  // Services report what they accept right now (e.g. a LibreTranslate server's own list)
+  const accepted = new Set(services.filter(s => s.enabled).flatMap(s => s.languages));
+  const available = languages.filter(l => l.code === 'auto' || accepted.has(l.code));
+
   sourceLang.innerHTML = '';
   targetLang.innerHTML = '';
-  for (const lang of languages) {
+  $('#firstLang').innerHTML = '';
+  $('#secondLang').innerHTML = '';
+  targetLang.add(new Option('自动', 'auto'));
+  for (const lang of available) {
     const opt1 = new Option(lang.name, lang.code);
     sourceLang.add(opt1);
     if (lang.code !== 'auto') {
       const opt2 = new Option(lang.name, lang.code);
       targetLang.add(opt2);
+      $('#firstLang').add(new Option(lang.name, lang.code));
+      $('#secondLang').add(new Option(lang.name, lang.code));
+    }
+  }
+}
+
+function populatePromptModes() {
+  for (const preset of promptPresets) {
+    promptMode.add(new Option(preset.name, preset.mode));
+    $('#templateMode').add(new Option(preset.name, preset.mode));
+  }
+}
+
+// The built-in template shows as the placeholder of an empty override
+function showTemplate(mode) {
+  const preset = promptPresets.find(p => p.mode === mode);
+  const custom = promptTemplates[mode] || {};
+  $('#templateSystem').placeholder = preset ? preset.system : '';
+  $('#templateUser').placeholder = preset ? preset.user : '';
+  $('#templateSystem').value = custom.system || '';
+  $('#templateUser').value = custom.user || '';
+  editingMode = mode;
+}
+
+function stashTemplate() {
+  const system = $('#templateSystem').value;
+  const user = $('#templateUser').value;
+  if (system.trim() || user.trim()) {
+    promptTemplates[editingMode] = { system, user };
+  } else {
+    delete promptTemplates[editingMode];
+  }
+}
+
+// Right-to-left languages (Arabic, Hebrew...) are laid out RTL; others follow their text
+function textDir(lang) {
+  return rtlLanguages.has(lang) ? 'rtl' : 'auto';
+}
+
+function updateInputDir() {
+  inputText.dir = textDir(sourceLang.value);
+}
+
+// Target rules are edited as "source -> target" lines
+function formatTargetRules(rules) {
+  return (rules || []).map(r => `${r.source} -> ${r.target}`).join('\n');
+}
+
+function parseTargetRules(text) {
+  return text.split('\n')
+    .map(line => line.split(/->|→/).map(s => s.trim()))
+    .filter(parts => parts.length === 2 && parts[0] && parts[1])
+    .map(([source, target]) => ({ source, target }));
+}
+
+// Usage limits are edited as "service = characters" lines
+function formatUsageLimits(limits) {
+  return Object.entries(limits || {}).map(([key, limit]) => `${key} = ${limit}`).join('\n');
+}
+
+function parseUsageLimits(text) {
+  const limits = {};
+  for (const line of text.split('\n')) {
+    const [key, limit] = line.split('=').map(s => s.trim());
+    const value = parseInt(limit, 10);
+    if (key && value > 0) limits[key] = value;
+  }
+  return limits;
+}
+
+async function loadUsage() {
+  const list = $('#usageList');
+  try {
+    const rows = await invoke('get_usage');
+    list.innerHTML = '';
+    for (const row of rows) {
+      const el = document.createElement('div');
+      el.className = 'usage-row';
+      const limit = row.limit ? ` / ${row.limit.toLocaleString()} (${row.percent}%)` : '';
+      const used = Math.max(row.characters, row.provider_count || 0);
+      el.innerHTML = `<span>${escapeHtml(row.name)} <small>${escapeHtml(row.key)}</small></span>`
+        + `<span>${used.toLocaleString()}${limit}</span>`;
+      if (row.percent >= ((config.usage && config.usage.warn_percent) || 80)) el.classList.add('over');
+      list.appendChild(el);
     }
+  } catch (e) {
+    list.textContent = '用量加载失败: ' + e;
   }
 }
 
+// Services whose official endpoint can be replaced by a `base_url`
+const BASE_URL_SERVICES = ['google', 'bing', 'deepl', 'baidu', 'youdao', 'tencent', 'volcano', 'caiyun'];
+
 function applyConfig(cfg) {
   sourceLang.value = cfg.general.source_lang || 'auto';
   targetLang.value = cfg.general.target_lang || 'zh-CN';
+  updateInputDir();
 
   // Settings page
   $('#themeSelect').value = cfg.general.theme || 'auto';
   $('#defaultTargetLang').value = cfg.general.target_lang || 'zh-CN';
+  $('#firstLang').value = cfg.general.first_lang || 'zh-CN';
+  $('#secondLang').value = cfg.general.second_lang || 'en';
+  $('#targetRules').value = formatTargetRules(cfg.general.target_rules);
   $('#googleEnabled').checked = cfg.services.google.enabled;
+  $('#googleApiKey').value = cfg.services.google.api_key || '';
+  $('#googleProjectId').value = cfg.services.google.project_id || '';
+  $('#googleLocation').value = cfg.services.google.location || '';
+  $('#googleGlossaryId').value = cfg.services.google.glossary_id || '';
   $('#bingEnabled').checked = cfg.services.bing.enabled;
+  $('#bingApiKey').value = cfg.services.bing.api_key || '';
+  $('#bingRegion').value = cfg.services.bing.region || '';
   $('#deeplEnabled').checked = cfg.services.deepl.enabled;
   $('#deeplApiKey').value = cfg.services.deepl.api_key || '';
+  $('#deeplFormality').value = cfg.services.deepl.formality || 'default';
+  $('#deeplEnglish').value = cfg.services.deepl.english_variant || 'EN-US';
+  $('#deeplPortuguese').value = cfg.services.deepl.portuguese_variant || 'PT-BR';
   $('#baiduEnabled').checked = cfg.services.baidu.enabled;
   $('#baiduAppId').value = cfg.services.baidu.app_id || '';
   $('#baiduSecretKey').value = cfg.services.baidu.secret_key || '';
-  $('#openaiEnabled').checked = cfg.services.openai.enabled;
-  $('#openaiApiKey').value = cfg.services.openai.api_key || '';
-  $('#openaiApiUrl').value = cfg.services.openai.api_url || 'https://api.openai.com/v1/chat/completions';
-  $('#openaiModel').value = cfg.services.openai.model || 'gpt-4o-mini';
+  $('#libreEnabled').checked = cfg.services.libre.enabled;
+  $('#libreBaseUrl').value = cfg.services.libre.base_url || '';
+  $('#libreApiKey').value = cfg.services.libre.api_key || '';
+  $('#youdaoEnabled').checked = cfg.services.youdao.enabled;
+  $('#youdaoAppKey').value = cfg.services.youdao.app_key || '';
+  $('#youdaoAppSecret').value = cfg.services.youdao.app_secret || '';
+  $('#tencentEnabled').checked = cfg.services.tencent.enabled;
+  $('#tencentSecretId').value = cfg.services.tencent.secret_id || '';
+  $('#tencentSecretKey').value = cfg.services.tencent.secret_key || '';
+  $('#tencentRegion').value = cfg.services.tencent.region || '';
+  $('#volcanoEnabled').checked = cfg.services.volcano.enabled;
+  $('#volcanoAccessKeyId').value = cfg.services.volcano.access_key_id || '';
+  $('#volcanoSecretAccessKey').value = cfg.services.volcano.secret_access_key || '';
+  $('#caiyunEnabled').checked = cfg.services.caiyun.enabled;
+  $('#caiyunToken').value = cfg.services.caiyun.api_key || '';
+  for (const id of BASE_URL_SERVICES) {
+    $(`#${id}BaseUrl`).value = cfg.services[id].base_url || '';
+  }
+  for (const id of ['anthropic', 'gemini']) {
+    const entry = cfg.services[id];
+    $(`#${id}Enabled`).checked = entry.enabled;
+    $(`#${id}ApiKey`).value = entry.api_key || '';
+    $(`#${id}ApiUrl`).value = entry.api_url || '';
+    $(`#${id}Model`).value = entry.model || '';
+  }
+  renderLlmProfiles(cfg.services.llm_profiles || []);
+  promptTemplates = { ...((cfg.prompts && cfg.prompts.templates) || {}) };
+  showTemplate($('#templateMode').value || 'translate');
+
+  const usage = cfg.usage || {};
+  $('#usageWarnPercent').value = usage.warn_percent || 80;
+  $('#usageAutoDisable').checked = !!usage.auto_disable;
+  $('#usageLimits').value = formatUsageLimits(usage.limits);
 
   // Select-translate settings
   if (cfg.select_translate) {
@@ -126,6 +279,21 @@ function setupEventListeners() {
   // OCR
   $('#ocrBtn').addEventListener('click', doOCR);
 
+  // New LLM profiles default to a local Ollama server
+  $('#addLlmProfileBtn').addEventListener('click', () => {
+    $('#llmProfiles').appendChild(createLlmProfileCard({
+      id: 'profile-' + Date.now().toString(36),
+      name: '',
+      enabled: true,
+      api_url: 'http://localhost:11434/v1/chat/completions',
+      api_key: '',
+      model: '',
+      temperature: 0.3,
+      max_tokens: 4096,
+      system_prompt: '',
+    }));
+  });
+
   // Swap languages
   $('#swapLangBtn').addEventListener('click', () => {
     const s = sourceLang.value;
@@ -133,8 +301,15 @@ function setupEventListeners() {
     if (s !== 'auto') {
       sourceLang.value = t;
       targetLang.value = s;
+      updateInputDir();
     }
   });
+  sourceLang.addEventListener('change', updateInputDir);
+
+  $('#templateMode').addEventListener('change', (e) => {
+    stashTemplate();
+    showTemplate(e.target.value);
+  });
 
   // Pin window
   $('#pinBtn').addEventListener('click', togglePin);
@@ -147,6 +322,7 @@ function setupEventListeners() {
   $('#settingsBtn').addEventListener('click', () => {
     mainView.classList.add('hidden');
     settingsView.classList.remove('hidden');
+    loadUsage();
   });
 
   $('#backBtn').addEventListener('click', () => {
@@ -263,6 +439,48 @@ function setupTauriListeners() {
     resultsSection.querySelector('.result-error').textContent = 'OCR 失败: ' + event.payload;
   });
 
+  // Streaming translation results, one event per finished service
+  listen('translate-partial', (event) => {
+    const { request_id, result } = event.payload;
+    if (request_id !== currentRequestId) return;
+    const card = appendResultCard(result);
+    const streaming = streamingCards.get(result.service);
+    if (streaming) {
+      streaming.replaceWith(card);
+      streamingCards.delete(result.service);
+    }
+  });
+
+  // Token-by-token output from streaming services (AI), appended to a live card
+  listen('translate-delta', (event) => {
+    const { request_id, service, delta } = event.payload;
+    if (request_id !== currentRequestId) return;
+    let card = streamingCards.get(service);
+    if (!card) {
+      card = appendResultCard({ service, translated: '', source_lang: sourceLang.value, target_lang: targetLang.value });
+      card.dataset.text = '';
+      streamingCards.set(service, card);
+    }
+    card.dataset.text += delta;
+    card.querySelector('.result-text').innerHTML = escapeHtml(card.dataset.text);
+  });
+
+  listen('translate-done', (event) => {
+    const { request_id, count } = event.payload;
+    if (request_id !== currentRequestId) return;
+    if (count === 0) showNoResults();
+  });
+
+  // A service passed its usage threshold; reload the config if it was switched off
+  listen('usage-threshold', async (event) => {
+    const { service, percent, disabled } = event.payload;
+    showToast(disabled ? `${service} 已达用量上限，已停用` : `${service} 本月用量已达 ${percent}%`);
+    if (disabled) {
+      config = await invoke('get_config');
+      applyConfig(config);
+    }
+  });
+
   // Clipboard monitoring: auto-translate mode sends text directly
   listen('clipboard-translate', async (event) => {
     settingsView.classList.add('hidden');
@@ -277,7 +495,12 @@ function setupTauriListeners() {
 
 // ==================== Translation ====================
 async function doTranslate() {
-  const text = inputText.value.trim();
+  // With part of the input selected, translate that part and send the rest as context
+  const full = inputText.value.trim();
+  const selected = inputText.value.slice(inputText.selectionStart, inputText.selectionEnd).trim();
+  const partial = selected && selected !== full;
+  const text = partial ? selected : full;
+  const context = partial ? full : '';
   if (!text || isTranslating) return;
 
   isTranslating = true;
@@ -285,85 +508,126 @@ async function doTranslate() {
   $('#translateBtn').disabled = true;
   resultsSection.innerHTML = '';
 
+  const requestId = ++currentRequestId;
+  streamingCards = new Map();
   try {
-    const results = await invoke('translate_text', {
+    await invoke('translate_text_stream', {
+      requestId,
       text,
       source: sourceLang.value,
       target: targetLang.value,
+      mode: promptMode.value || 'translate',
+      context,
     });
-
-    renderResults(results);
   } catch (e) {
     resultsSection.innerHTML = `<div class="result-card"><div class="result-body"><span class="result-error">翻译出错: ${e}</span></div></div>`;
   } finally {
-    isTranslating = false;
-    loadingBar.classList.remove('active');
-    $('#translateBtn').disabled = false;
+    if (requestId === currentRequestId) {
+      isTranslating = false;
+      loadingBar.classList.remove('active');
+      $('#translateBtn').disabled = false;
+    }
   }
 }
 
-function renderResults(results) {
-  resultsSection.innerHTML = '';
-
-  if (!results || results.length === 0) {
-    resultsSection.innerHTML = '<div class="result-card"><div class="result-body"><span class="result-error">没有翻译结果</span></div></div>';
-    return;
-  }
+function showNoResults() {
+  resultsSection.innerHTML = '<div class="result-card"><div class="result-body"><span class="result-error">没有翻译结果</span></div></div>';
+}
 
-  for (const r of results) {
-    const iconClass = getServiceIconClass(r.service);
-    const iconLabel = getServiceIconLabel(r.service);
-
-    const card = document.createElement('div');
-    card.className = 'result-card';
-    card.innerHTML = `
-      <div class="result-header">
-        <div class="result-service">
-          <span class="result-service-icon ${iconClass}">${iconLabel}</span>
-          <span class="result-service-name">${r.service}</span>
-        </div>
-        <div class="result-actions">
-          <button class="result-action-btn copy-btn" title="复制">
-            <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2">
-              <rect x="9" y="9" width="13" height="13" rx="2" ry="2"/><path d="M5 15H4a2 2 0 01-2-2V4a2 2 0 012-2h9a2 2 0 012 2v1"/>
-            </svg>
-          </button>
-          <button class="result-action-btn speak-result-btn" title="朗读">
-            <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2">
-              <polygon points="11 5 6 9 2 9 2 15 6 15 11 19 11 5"/><path d="M15.54 8.46a5 5 0 010 7.07"/>
-            </svg>
-          </button>
-        </div>
+function appendResultCard(r) {
+  const iconClass = getServiceIconClass(r.service);
+  const iconLabel = getServiceIconLabel(r.service);
+
+  const card = document.createElement('div');
+  card.className = 'result-card';
+  card.innerHTML = `
+    <div class="result-header">
+      <div class="result-service">
+        <span class="result-service-icon ${iconClass}">${iconLabel}</span>
+        <span class="result-service-name">${r.service}</span>
       </div>
-      <div class="result-body">
-        ${r.error
-        ? `<span class="result-error">${r.error}</span>`
-        : `<div class="result-text">${escapeHtml(r.translated)}</div>`
-      }
-        <div class="result-lang-info">${r.source_lang} → ${r.target_lang}</div>
+      <div class="result-actions">
+        <button class="result-action-btn copy-btn" title="复制">
+          <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2">
+            <rect x="9" y="9" width="13" height="13" rx="2" ry="2"/><path d="M5 15H4a2 2 0 01-2-2V4a2 2 0 012-2h9a2 2 0 012 2v1"/>
+          </svg>
+        </button>
+        <button class="result-action-btn speak-result-btn" title="朗读">
+          <svg width="14" height="14" viewBox="0 0 24 24" fill="none" stroke="currentColor" stroke-width="2">
+            <polygon points="11 5 6 9 2 9 2 15 6 15 11 19 11 5"/><path d="M15.54 8.46a5 5 0 010 7.07"/>
+          </svg>
+        </button>
       </div>
-    `;
+    </div>
+    <div class="result-body">
+      ${r.error
+      ? `<span class="result-error">${escapeHtml(formatError(r.error))}</span>`
+      : r.dictionary && r.service === 'Dictionary'
+        ? renderDictionary(r.dictionary)
+        : `<div class="result-text" dir="${textDir(r.target_lang)}">${escapeHtml(r.translated)}</div>${r.dictionary ? renderDictionary(r.dictionary) : ''}`
+    }
+      <div class="result-lang-info">${r.source_lang} → ${r.target_lang}</div>
+    </div>
+  `;
 
-    // Copy button
-    card.querySelector('.copy-btn').addEventListener('click', () => {
-      navigator.clipboard.writeText(r.translated).then(() => showToast('已复制'));
-    });
+  // Copy button
+  card.querySelector('.copy-btn').addEventListener('click', () => {
+    navigator.clipboard.writeText(r.translated).then(() => showToast('已复制'));
+  });
 
-    // Speak button
-    card.querySelector('.speak-result-btn').addEventListener('click', () => {
-      if (r.translated) invoke('speak', { text: r.translated });
-    });
+  // Speak button
+  card.querySelector('.speak-result-btn').addEventListener('click', () => {
+    if (r.translated) invoke('speak', { text: r.translated });
+  });
+
+  resultsSection.appendChild(card);
+  return card;
+}
 
-    resultsSection.appendChild(card);
+// Guidance for a typed TranslateError ({ kind, ... }) from the backend
+function formatError(err) {
+  if (typeof err === 'string') return err;
+  switch (err.kind) {
+    case 'not_configured': return '未配置 API 密钥，请在设置中填写';
+    case 'network': return `网络错误，请检查网络连接 (${err.message})`;
+    case 'timeout': return '请求超时，请稍后重试';
+    case 'http': return `服务返回 HTTP ${err.status}: ${err.message}`;
+    case 'auth': return `认证失败，请检查 API 密钥是否正确 (${err.message})`;
+    case 'quota': return `额度已用尽，请检查账户余额 (${err.message})`;
+    case 'rate_limit':
+      return err.retry_after ? `请求过于频繁，请 ${err.retry_after} 秒后重试` : '请求过于频繁，请稍后重试';
+    case 'parse': return `无法解析服务响应 (${err.message})`;
+    case 'unsupported_language': return `该服务不支持此语言 (${err.lang})`;
+    case 'provider': return err.code ? `错误 ${err.code}: ${err.message}` : err.message;
+    default: return err.message || String(err.kind);
   }
 }
 
+function renderDictionary(entry) {
+  const phonetics = entry.phonetics.length
+    ? `<div class="dict-phonetics">${entry.phonetics.map(escapeHtml).join(' ')}</div>`
+    : '';
+  const meanings = entry.meanings.map((m) => `
+    <div class="dict-meaning">
+      ${m.part_of_speech ? `<span class="dict-pos">${escapeHtml(m.part_of_speech)}</span>` : ''}${escapeHtml(m.definitions.join('; '))}
+    </div>`).join('');
+  const examples = entry.examples.map((e) => `<div class="dict-example">${escapeHtml(e)}</div>`).join('');
+  return `<div class="dict-entry">${phonetics}${meanings}${examples}</div>`;
+}
+
 function getServiceIconClass(service) {
   const s = service.toLowerCase();
   if (s.includes('google')) return 'google';
   if (s.includes('bing')) return 'bing';
   if (s.includes('deepl')) return 'deepl';
   if (s.includes('baidu') || s.includes('百度')) return 'baidu';
+  if (s.includes('libre')) return 'libre';
+  if (s.includes('youdao')) return 'youdao';
+  if (s.includes('tencent')) return 'tencent';
+  if (s.includes('volcano')) return 'volcano';
+  if (s.includes('caiyun')) return 'caiyun';
+  if (s.includes('anthropic')) return 'anthropic';
+  if (s.includes('gemini')) return 'gemini';
   if (s.includes('ai')) return 'ai';
   return 'google';
 }
@@ -374,6 +638,13 @@ function getServiceIconLabel(service) {
   if (s.includes('bing')) return 'B';
   if (s.includes('deepl')) return 'D';
   if (s.includes('baidu') || s.includes('百度')) return '百';
+  if (s.includes('libre')) return 'L';
+  if (s.includes('youdao')) return '有';
+  if (s.includes('tencent')) return '腾';
+  if (s.includes('volcano')) return '火';
+  if (s.includes('caiyun')) return '彩';
+  if (s.includes('anthropic')) return 'A';
+  if (s.includes('gemini')) return 'Ge';
   if (s.includes('ai')) return 'AI';
   return '?';
 }
@@ -400,21 +671,71 @@ async function doOCR() {
 async function saveSettings() {
   config.general.theme = $('#themeSelect').value;
   config.general.target_lang = $('#defaultTargetLang').value;
+  config.general.first_lang = $('#firstLang').value;
+  config.general.second_lang = $('#secondLang').value;
+  config.general.target_rules = parseTargetRules($('#targetRules').value);
 
   config.services.google.enabled = $('#googleEnabled').checked;
+  config.services.google.api_key = $('#googleApiKey').value.trim();
+  config.services.google.project_id = $('#googleProjectId').value.trim();
+  config.services.google.location = $('#googleLocation').value.trim() || 'global';
+  config.services.google.glossary_id = $('#googleGlossaryId').value.trim();
   config.services.bing.enabled = $('#bingEnabled').checked;
+  config.services.bing.api_key = $('#bingApiKey').value.trim();
+  config.services.bing.region = $('#bingRegion').value.trim();
 
   config.services.deepl.enabled = $('#deeplEnabled').checked;
   config.services.deepl.api_key = $('#deeplApiKey').value;
+  config.services.deepl.formality = $('#deeplFormality').value;
+  config.services.deepl.english_variant = $('#deeplEnglish').value;
+  config.services.deepl.portuguese_variant = $('#deeplPortuguese').value;
 
   config.services.baidu.enabled = $('#baiduEnabled').checked;
   config.services.baidu.app_id = $('#baiduAppId').value;
   config.services.baidu.secret_key = $('#baiduSecretKey').value;
 
-  config.services.openai.enabled = $('#openaiEnabled').checked;
-  config.services.openai.api_key = $('#openaiApiKey').value;
-  config.services.openai.api_url = $('#openaiApiUrl').value;
-  config.services.openai.model = $('#openaiModel').value;
+  config.services.libre.enabled = $('#libreEnabled').checked;
+  config.services.libre.base_url = $('#libreBaseUrl').value.trim();
+  config.services.libre.api_key = $('#libreApiKey').value;
+
+  config.services.youdao.enabled = $('#youdaoEnabled').checked;
+  config.services.youdao.app_key = $('#youdaoAppKey').value;
+  config.services.youdao.app_secret = $('#youdaoAppSecret').value;
+
+  config.services.tencent.enabled = $('#tencentEnabled').checked;
+  config.services.tencent.secret_id = $('#tencentSecretId').value;
+  config.services.tencent.secret_key = $('#tencentSecretKey').value;
+  config.services.tencent.region = $('#tencentRegion').value.trim() || 'ap-guangzhou';
+
+  config.services.volcano.enabled = $('#volcanoEnabled').checked;
+  config.services.volcano.access_key_id = $('#volcanoAccessKeyId').value;
+  config.services.volcano.secret_access_key = $('#volcanoSecretAccessKey').value;
+
+  config.services.caiyun.enabled = $('#caiyunEnabled').checked;
+  config.services.caiyun.api_key = $('#caiyunToken').value;
+  for (const id of BASE_URL_SERVICES) {
+    config.services[id].base_url = $(`#${id}BaseUrl`).value.trim();
+  }
+
+  for (const id of ['anthropic', 'gemini']) {
+    config.services[id] = {
+      ...config.services[id],
+      enabled: $(`#${id}Enabled`).checked,
+      api_key: $(`#${id}ApiKey`).value,
+      api_url: $(`#${id}ApiUrl`).value.trim(),
+      model: $(`#${id}Model`).value.trim(),
+    };
+  }
+  config.services.llm_profiles = collectLlmProfiles();
+
+  stashTemplate();
+  config.prompts = { templates: promptTemplates };
+
+  config.usage = {
+    warn_percent: Math.min(100, Math.max(1, parseInt($('#usageWarnPercent').value, 10) || 80)),
+    auto_disable: $('#usageAutoDisable').checked,
+    limits: parseUsageLimits($('#usageLimits').value),
+  };
 
   // Select-translate settings
   if (!config.select_translate) {
@@ -441,13 +762,87 @@ async function saveSettings() {
   try {
     await invoke('update_config', { config });
     await invoke('update_shortcuts');
+    const source = sourceLang.value;
+    await populateLanguageSelects();
+    sourceLang.value = source;
     targetLang.value = config.general.target_lang;
+    $('#firstLang').value = config.general.first_lang;
+    $('#secondLang').value = config.general.second_lang;
+    updateInputDir();
     showToast('设置已保存');
   } catch (e) {
     showToast('保存失败: ' + e);
   }
 }
 
+// ==================== LLM Profiles ====================
+function renderLlmProfiles(profiles) {
+  const container = $('#llmProfiles');
+  container.innerHTML = '';
+  for (const profile of profiles) {
+    container.appendChild(createLlmProfileCard(profile));
+  }
+}
+
+function createLlmProfileCard(profile) {
+  const item = document.createElement('div');
+  item.className = 'service-item llm-profile';
+  item.dataset.id = profile.id;
+  item.innerHTML = `
+    <div class="service-header">
+      <div class="service-info">
+        <span class="service-icon ai">AI</span>
+        <input type="text" class="setting-input profile-name" placeholder="名称" />
+      </div>
+      <div class="profile-actions">
+        <button class="profile-delete-btn" title="删除">×</button>
+        <label class="toggle">
+          <input type="checkbox" class="profile-enabled">
+          <span class="toggle-slider"></span>
+        </label>
+      </div>
+    </div>
+    <div class="service-config">
+      <input type="text" class="setting-input profile-url" placeholder="API URL" />
+      <input type="password" class="setting-input profile-key" placeholder="API Key（本地模型可留空）" />
+      <input type="text" class="setting-input profile-model" placeholder="模型名称" />
+      <input type="number" class="setting-input profile-temperature" min="0" max="2" step="0.1" placeholder="温度" />
+      <input type="number" class="setting-input profile-max-tokens" min="1" step="1" placeholder="最大输出 Token" />
+      <textarea class="setting-input setting-textarea profile-prompt" rows="3"
+        placeholder="翻译模式的系统提示词，仅用于此配置；留空使用「AI 提示词」中的模板"></textarea>
+    </div>
+  `;
+  item.querySelector('.profile-name').value = profile.name || '';
+  item.querySelector('.profile-enabled').checked = profile.enabled;
+  item.querySelector('.profile-url').value = profile.api_url || '';
+  item.querySelector('.profile-key').value = profile.api_key || '';
+  item.querySelector('.profile-model').value = profile.model || '';
+  item.querySelector('.profile-temperature').value = profile.temperature ?? 0.3;
+  item.querySelector('.profile-max-tokens').value = profile.max_tokens ?? 4096;
+  item.querySelector('.profile-prompt').value = profile.system_prompt || '';
+  item.querySelector('.profile-delete-btn').addEventListener('click', () => item.remove());
+  return item;
+}
+
+function collectLlmProfiles() {
+  return [...document.querySelectorAll('.llm-profile')].map(item => {
+    const model = item.querySelector('.profile-model').value.trim();
+    const temperature = parseFloat(item.querySelector('.profile-temperature').value);
+    const maxTokens = parseInt(item.querySelector('.profile-max-tokens').value, 10);
+    return {
+      id: item.dataset.id,
+      name: item.querySelector('.profile-name').value.trim() || model,
+      enabled: item.querySelector('.profile-enabled').checked,
+      api_url: item.querySelector('.profile-url').value.trim(),
+      api_key: item.querySelector('.profile-key').value,
+      model,
+      temperature: Number.isFinite(temperature) ? temperature : 0.3,
+      max_tokens: maxTokens > 0 ? maxTokens : 4096,
+      system_prompt: item.querySelector('.profile-prompt').value,
+    };
+  });
+}
+
 // ==================== Window Pin ====================
 async function togglePin() {
   isPinned = !isPinned;